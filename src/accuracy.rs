@@ -0,0 +1,136 @@
+//! Specified accuracy depending on the ambient and object temperatures
+
+use crate::{ic, Error, Mlx9061x};
+use embedded_hal::i2c::I2c;
+
+/// Device accuracy grade
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    /// Standard accuracy
+    Standard,
+    /// Medical accuracy (e.g. MLX90614DCx)
+    Medical,
+}
+
+/// Device variant for the accuracy lookup
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccuracyVariant {
+    /// MLX90614 of the given grade
+    Mlx90614(Grade),
+    /// MLX90615 of the given grade
+    Mlx90615(Grade),
+}
+
+/// Object temperature measurement together with its specified tolerance
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    /// Object temperature in celsius degrees
+    pub object: f32,
+    /// Ambient temperature in celsius degrees
+    pub ambient: f32,
+    /// Specified ± tolerance in celsius degrees
+    ///
+    /// `None` if the temperatures are outside of the specified range.
+    pub tolerance: Option<f32>,
+}
+
+/// Accuracy zone: (Ta min, Ta max, To min, To max, ± tolerance)
+type Zone = (f32, f32, f32, f32, f32);
+
+const MLX90614_MEDICAL: &[Zone] = &[(16.0, 40.0, 22.0, 40.0, 0.2)];
+const MLX90614_STANDARD: &[Zone] = &[
+    (0.0, 50.0, 0.0, 60.0, 0.5),
+    (0.0, 50.0, -20.0, 120.0, 1.0),
+    (-40.0, 85.0, -40.0, 120.0, 2.0),
+    (-40.0, 125.0, -70.0, 380.0, 4.0),
+];
+const MLX90615_MEDICAL: &[Zone] = &[(16.0, 40.0, 32.0, 42.0, 0.2)];
+const MLX90615_STANDARD: &[Zone] = &[
+    (0.0, 50.0, 0.0, 70.0, 0.5),
+    (0.0, 50.0, -20.0, 85.0, 1.0),
+    (-40.0, 85.0, -40.0, 115.0, 2.0),
+];
+
+fn lookup(zones: &[Zone], ta: f32, to: f32) -> Option<f32> {
+    zones
+        .iter()
+        .find(|(ta_min, ta_max, to_min, to_max, _)| {
+            ta >= *ta_min && ta <= *ta_max && to >= *to_min && to <= *to_max
+        })
+        .map(|zone| zone.4)
+}
+
+/// Get the specified ± accuracy in celsius degrees for the given temperatures
+///
+/// `ta` and `to` are the ambient and object temperatures in celsius degrees.
+/// The zones approximate the accuracy figures in the datasheets.
+/// Returns `None` if the temperatures are outside of the specified range.
+pub fn accuracy(ta: f32, to: f32, variant: AccuracyVariant) -> Option<f32> {
+    let (grade, medical, standard) = match variant {
+        AccuracyVariant::Mlx90614(grade) => (grade, MLX90614_MEDICAL, MLX90614_STANDARD),
+        AccuracyVariant::Mlx90615(grade) => (grade, MLX90615_MEDICAL, MLX90615_STANDARD),
+    };
+    let medical = match grade {
+        Grade::Medical => lookup(medical, ta, to),
+        Grade::Standard => None,
+    };
+    medical.or_else(|| lookup(standard, ta, to))
+}
+
+macro_rules! uncertainty {
+    ($ic_marker:ident, $variant:ident, $object_method:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Read the object and ambient temperatures together with the specified
+            /// tolerance for the given grade
+            ///
+            /// This overrides the grade used by `measure_with_uncertainty()`. See `accuracy()`.
+            pub fn measure_with_uncertainty_for_grade(
+                &mut self,
+                grade: Grade,
+            ) -> Result<Measurement, Error<E>> {
+                let object = self.$object_method()?;
+                let ambient = self.ambient_temperature()?;
+                Ok(Measurement {
+                    object,
+                    ambient,
+                    tolerance: accuracy(ambient, object, AccuracyVariant::$variant(grade)),
+                })
+            }
+        }
+    };
+}
+uncertainty!(Mlx90614, Mlx90614, object1_temperature);
+uncertainty!(Mlx90615, Mlx90615, object_temperature);
+
+impl<E, I2C> Mlx9061x<I2C, ic::Mlx90614>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the object 1 and ambient temperatures together with the specified tolerance
+    ///
+    /// The grade is taken from the part variant. See `variant()` and `accuracy()`.
+    pub fn measure_with_uncertainty(&mut self) -> Result<Measurement, Error<E>> {
+        let grade = self.variant()?.grade;
+        self.measure_with_uncertainty_for_grade(grade)
+    }
+}
+
+impl<E, I2C> Mlx9061x<I2C, ic::Mlx90615>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the object and ambient temperatures together with the specified tolerance
+    ///
+    /// The MLX90615 grade cannot be detected so the standard grade is assumed.
+    /// Use `measure_with_uncertainty_for_grade()` for medical grade parts.
+    /// See `accuracy()`.
+    pub fn measure_with_uncertainty(&mut self) -> Result<Measurement, Error<E>> {
+        self.measure_with_uncertainty_for_grade(Grade::Standard)
+    }
+}
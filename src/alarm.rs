@@ -0,0 +1,150 @@
+//! Threshold alarms with hysteresis and debounce
+
+use crate::Error;
+
+/// Alarm configuration
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlarmConfig {
+    /// Readings above this value raise the high alarm
+    pub high: f32,
+    /// Readings below this value raise the low alarm
+    pub low: f32,
+    /// Distance from the threshold that readings need to go back before clearing an alarm
+    pub hysteresis: f32,
+    /// Number of consecutive readings necessary to change state (at least one)
+    pub debounce: u8,
+}
+
+/// Alarm threshold
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmLevel {
+    /// High threshold
+    High,
+    /// Low threshold
+    Low,
+}
+
+/// Alarm state
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmState {
+    /// Readings are within the thresholds
+    Normal,
+    /// An alarm is raised
+    Raised(AlarmLevel),
+    /// Readings fail. e.g. PEC mismatch or measurement flagged as erroneous
+    SensorFault,
+}
+
+/// Alarm state change
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmEvent {
+    /// An alarm was raised
+    ///
+    /// This implicitly clears the previous state. e.g. another alarm or a sensor fault.
+    Raised(AlarmLevel),
+    /// An alarm was cleared and readings are back within the thresholds
+    Cleared(AlarmLevel),
+    /// Readings started failing
+    SensorFault,
+    /// Readings are valid again and within the thresholds
+    SensorRecovered,
+}
+
+/// Over/under-temperature alarm state machine
+///
+/// Feed it with the result of any of the driver temperature reading methods.
+/// Failed readings are not treated as temperatures but lead to the `SensorFault` state.
+/// All state changes are debounced.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alarm {
+    config: AlarmConfig,
+    state: AlarmState,
+    pending: AlarmState,
+    pending_count: u8,
+}
+
+impl Alarm {
+    /// Create a new alarm in the `Normal` state
+    ///
+    /// Returns `None` if the low threshold is not below the high threshold
+    /// or the hysteresis is negative.
+    pub fn new(config: AlarmConfig) -> Option<Self> {
+        if config.low >= config.high || config.hysteresis < 0.0 {
+            return None;
+        }
+        Some(Alarm {
+            config,
+            state: AlarmState::Normal,
+            pending: AlarmState::Normal,
+            pending_count: 0,
+        })
+    }
+
+    /// Get the current state
+    pub fn state(&self) -> AlarmState {
+        self.state
+    }
+
+    /// Go back to the `Normal` state
+    pub fn reset(&mut self) {
+        self.state = AlarmState::Normal;
+        self.pending = AlarmState::Normal;
+        self.pending_count = 0;
+    }
+
+    /// Process a reading and return the state change, if any
+    pub fn update<E>(&mut self, reading: Result<f32, Error<E>>) -> Option<AlarmEvent> {
+        let candidate = match reading {
+            Ok(t) => self.evaluate(t),
+            Err(_) => AlarmState::SensorFault,
+        };
+        if candidate == self.state {
+            self.pending_count = 0;
+            return None;
+        }
+        if candidate == self.pending && self.pending_count != 0 {
+            self.pending_count = self.pending_count.saturating_add(1);
+        } else {
+            self.pending = candidate;
+            self.pending_count = 1;
+        }
+        if self.pending_count < self.config.debounce.max(1) {
+            return None;
+        }
+        let previous = self.state;
+        self.state = candidate;
+        self.pending_count = 0;
+        Some(match (previous, candidate) {
+            (_, AlarmState::Raised(level)) => AlarmEvent::Raised(level),
+            (_, AlarmState::SensorFault) => AlarmEvent::SensorFault,
+            (AlarmState::Raised(level), AlarmState::Normal) => AlarmEvent::Cleared(level),
+            (_, AlarmState::Normal) => AlarmEvent::SensorRecovered,
+        })
+    }
+
+    fn evaluate(&self, t: f32) -> AlarmState {
+        let AlarmConfig {
+            high,
+            low,
+            hysteresis,
+            ..
+        } = self.config;
+        let fresh = if t > high {
+            AlarmState::Raised(AlarmLevel::High)
+        } else if t < low {
+            AlarmState::Raised(AlarmLevel::Low)
+        } else {
+            AlarmState::Normal
+        };
+        match self.state {
+            AlarmState::Raised(AlarmLevel::High) if t >= high - hysteresis => self.state,
+            AlarmState::Raised(AlarmLevel::Low) if t <= low + hysteresis => self.state,
+            _ => fresh,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! User calibration of the object temperature against a reference source
+
+use crate::{ic, Error, Mlx9061x};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Linear user calibration of the object temperature
+///
+/// The calibrated temperature is computed as `gain * measured + offset`.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    gain: f32,
+    offset: f32,
+}
+
+/// Averaged measurement of a reference source at a known temperature
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationPoint {
+    /// Temperature measured by the device in celsius degrees
+    pub measured: f32,
+    /// Actual temperature of the reference source in celsius degrees
+    pub reference: f32,
+}
+
+impl Default for Calibration {
+    /// Identity calibration (gain 1.0, offset 0.0)
+    fn default() -> Self {
+        Calibration::new(1.0, 0.0)
+    }
+}
+
+impl Calibration {
+    /// Create a calibration from a gain and an offset in celsius degrees
+    pub const fn new(gain: f32, offset: f32) -> Self {
+        Calibration { gain, offset }
+    }
+
+    /// Fit a calibration to two measurements of a reference source
+    ///
+    /// Returns `None` if both points were measured at the same temperature.
+    pub fn from_two_points(low: CalibrationPoint, high: CalibrationPoint) -> Option<Self> {
+        let measured_span = high.measured - low.measured;
+        if measured_span == 0.0 {
+            return None;
+        }
+        let gain = (high.reference - low.reference) / measured_span;
+        let offset = low.reference - gain * low.measured;
+        Some(Calibration::new(gain, offset))
+    }
+
+    /// Get the gain
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Get the offset in celsius degrees
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// Apply the calibration to a temperature in celsius degrees
+    pub fn apply(&self, temperature: f32) -> f32 {
+        self.gain * temperature + self.offset
+    }
+
+    /// Serialize the coefficients as little-endian gain and offset
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[..4].copy_from_slice(&self.gain.to_le_bytes());
+        bytes[4..].copy_from_slice(&self.offset.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialize the coefficients written by `to_bytes()`
+    pub fn from_bytes(bytes: [u8; 8]) -> Self {
+        let gain = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let offset = f32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        Calibration::new(gain, offset)
+    }
+}
+
+/// Driver wrapper applying a user calibration to every object temperature reading
+#[derive(Debug)]
+pub struct Calibrated<I2C, IC> {
+    sensor: Mlx9061x<I2C, IC>,
+    calibration: Calibration,
+}
+
+impl<I2C, IC> Calibrated<I2C, IC> {
+    /// Wrap a driver instance
+    pub fn new(sensor: Mlx9061x<I2C, IC>, calibration: Calibration) -> Self {
+        Calibrated {
+            sensor,
+            calibration,
+        }
+    }
+
+    /// Destroy the wrapper, return the driver instance
+    pub fn destroy(self) -> Mlx9061x<I2C, IC> {
+        self.sensor
+    }
+
+    /// Get the wrapped driver instance for uncalibrated operations
+    pub fn sensor_mut(&mut self) -> &mut Mlx9061x<I2C, IC> {
+        &mut self.sensor
+    }
+
+    /// Get the current calibration
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Set the calibration
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+}
+
+macro_rules! calibration {
+    ($ic_marker:ident, $object_method:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Measure a reference source at a known temperature in celsius degrees
+            ///
+            /// The object temperature will be read `samples` times, waiting `interval_ms`
+            /// milliseconds in between, and the average returned.
+            /// Zero samples will return `Error::InvalidInputData`.
+            pub fn measure_calibration_point<D: DelayNs>(
+                &mut self,
+                reference: f32,
+                samples: u8,
+                interval_ms: u32,
+                delay: &mut D,
+            ) -> Result<CalibrationPoint, Error<E>> {
+                if samples == 0 {
+                    return Err(Error::InvalidInputData);
+                }
+                let mut sum = 0.0;
+                for i in 0..samples {
+                    if i != 0 {
+                        delay.delay_ms(interval_ms);
+                    }
+                    sum += self.$object_method()?;
+                }
+                Ok(CalibrationPoint {
+                    measured: sum / f32::from(samples),
+                    reference,
+                })
+            }
+        }
+    };
+}
+calibration!(Mlx90614, object1_temperature);
+calibration!(Mlx90615, object_temperature);
+
+macro_rules! calibrated_read {
+    ($ic_marker:ident, $( $(#[$attr:meta])* $method:ident ),*) => {
+        impl<E, I2C> Calibrated<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            $(
+                $(#[$attr])*
+                pub fn $method(&mut self) -> Result<f32, Error<E>> {
+                    let t = self.sensor.$method()?;
+                    Ok(self.calibration.apply(t))
+                }
+            )*
+        }
+    };
+}
+calibrated_read!(
+    Mlx90614,
+    /// Read the calibrated object 1 temperature in celsius degrees
+    object1_temperature,
+    /// Read the calibrated object 2 temperature in celsius degrees
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    object2_temperature
+);
+calibrated_read!(
+    Mlx90615,
+    /// Read the calibrated object temperature in celsius degrees
+    object_temperature
+);
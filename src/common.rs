@@ -1,15 +1,29 @@
 use crate::{
     ic,
     register_access::{mlx90614, mlx90615},
-    Error, Mlx9061x, SlaveAddr,
+    Error, Mlx9061x, PendingAddressChange, SlaveAddr,
 };
-use embedded_hal::{delay::DelayNs, i2c::I2c};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+    i2c::{self, ErrorKind, I2c, NoAcknowledgeSource},
+};
+
+/// Half of a 100kHz SCL clock period in microseconds
+const HALF_CLOCK_PERIOD_US: u32 = 5;
 
 impl<I2C, IC> Mlx9061x<I2C, IC> {
     /// Destroy driver instance, return I²C bus.
     pub fn destroy(self) -> I2C {
         self.i2c
     }
+
+    /// Use the new address after the device has been power cycled
+    ///
+    /// See `set_address()`.
+    pub fn confirm_address_change(&mut self, change: PendingAddressChange) {
+        self.address = change.new;
+    }
 }
 
 macro_rules! common {
@@ -23,15 +37,51 @@ macro_rules! common {
             /// The address will be stored in the EEPROM.
             /// The address will be first cleared, before the new one is written.
             /// After each write the configured delay will be waited except the last time.
+            ///
+            /// Reserved I²C addresses (0x00-0x07 and 0x78-0x7F) and `SlaveAddr::Broadcast`
+            /// return `Error::InvalidAddress` and an address where another device answers
+            /// returns `Error::AddressInUse`. This is probed by reading the ambient temperature
+            /// register at the new address. Only a not acknowledged address means the address
+            /// is free. If the address is acknowledged but the command byte is not, another
+            /// device answers there. Other probe errors, including a not acknowledged probe
+            /// from an unknown source, are returned as `Error::I2C`.
+            ///
+            /// The device keeps answering at the old address until it is power cycled,
+            /// so the driver keeps using it until the returned change is passed to
+            /// `confirm_address_change()`.
             pub fn set_address<D: DelayNs>(
                 &mut self,
                 address: SlaveAddr,
                 delay_ms: &mut D,
-            ) -> Result<(), Error<E>> {
+            ) -> Result<PendingAddressChange, Error<E>>
+            where
+                E: i2c::Error,
+            {
+                if address == SlaveAddr::Broadcast {
+                    return Err(Error::InvalidAddress(0x00));
+                }
                 let address = Self::get_address(address, $ic_reg::DEV_ADDR)?;
+                if address != self.address {
+                    let mut data = [0; 3];
+                    match self
+                        .i2c
+                        .write_read(address, &[$ic_reg::Register::TA], &mut data)
+                    {
+                        Ok(()) => return Err(Error::AddressInUse(address)),
+                        Err(e) => match e.kind() {
+                            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => (),
+                            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => {
+                                return Err(Error::AddressInUse(address))
+                            }
+                            _ => self.count_i2c_error(Err(e))?,
+                        },
+                    }
+                }
                 self.write_u16_eeprom($ic_reg::Register::ADDRESS, u16::from(address), delay_ms)?;
-                self.address = address;
-                Ok(())
+                Ok(PendingAddressChange {
+                    old: self.address,
+                    new: address,
+                })
             }
 
             /// Enter sleep mode
@@ -46,3 +96,43 @@ macro_rules! common {
 }
 common!(Mlx90614, mlx90614);
 common!(Mlx90615, mlx90615);
+
+/// Free a bus where a device holds SDA low after an interrupted transfer.
+///
+/// SCL is toggled up to nine times until SDA is released and then a STOP
+/// condition is issued. SDA must be an open-drain pin which can be read back.
+/// Like for `wake_mlx90614()`, destroy the driver to get the SCL/SDA pins back.
+///
+/// Returns whether SDA is released afterwards.
+pub fn recover_bus<E, SclPin, SdaPin, D>(
+    scl: &mut SclPin,
+    sda: &mut SdaPin,
+    delay: &mut D,
+) -> Result<bool, E>
+where
+    SclPin: OutputPin<Error = E>,
+    SdaPin: InputPin<Error = E> + OutputPin<Error = E>,
+    D: DelayNs,
+{
+    sda.set_high()?;
+    scl.set_high()?;
+    delay.delay_us(HALF_CLOCK_PERIOD_US);
+    for _ in 0..9 {
+        if sda.is_high()? {
+            break;
+        }
+        scl.set_low()?;
+        delay.delay_us(HALF_CLOCK_PERIOD_US);
+        scl.set_high()?;
+        delay.delay_us(HALF_CLOCK_PERIOD_US);
+    }
+    // STOP condition: SDA rising while SCL is high
+    scl.set_low()?;
+    sda.set_low()?;
+    delay.delay_us(HALF_CLOCK_PERIOD_US);
+    scl.set_high()?;
+    delay.delay_us(HALF_CLOCK_PERIOD_US);
+    sda.set_high()?;
+    delay.delay_us(HALF_CLOCK_PERIOD_US);
+    sda.is_high()
+}
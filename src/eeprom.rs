@@ -0,0 +1,568 @@
+//! EEPROM images
+
+use crate::{
+    ic,
+    register_access::{mlx90614, mlx90615},
+    Error, Mlx9061x, Variant,
+};
+use core::fmt;
+use embedded_hal::i2c::I2c;
+
+/// Maximum number of EEPROM cells
+const MAX_CELLS: usize = 32;
+
+/// EEPROM layout of a device
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EepromLayout {
+    /// MLX90614: 32 cells
+    Mlx90614,
+    /// MLX90615: 16 cells
+    Mlx90615,
+}
+
+impl EepromLayout {
+    /// Number of EEPROM cells
+    pub fn cell_count(self) -> usize {
+        match self {
+            EepromLayout::Mlx90614 => 32,
+            EepromLayout::Mlx90615 => 16,
+        }
+    }
+
+    /// Name of the cell at the given address
+    pub fn cell_name(self, address: u8) -> Option<&'static str> {
+        if usize::from(address) >= self.cell_count() {
+            return None;
+        }
+        let name = match (self, address) {
+            (EepromLayout::Mlx90614, 0x00) => "ToMax",
+            (EepromLayout::Mlx90614, 0x01) => "ToMin",
+            (EepromLayout::Mlx90614, 0x02) => "PwmCtrl",
+            (EepromLayout::Mlx90614, 0x03) => "TaRange",
+            (EepromLayout::Mlx90614, 0x04) => "Emissivity",
+            (EepromLayout::Mlx90614, 0x05) => "Config1",
+            (EepromLayout::Mlx90614, 0x0E) => "Address",
+            (EepromLayout::Mlx90614, 0x0F) => "EmissivityCalibration",
+            (EepromLayout::Mlx90614, 0x1C) => "Id0",
+            (EepromLayout::Mlx90614, 0x1D) => "Id1",
+            (EepromLayout::Mlx90614, 0x1E) => "Id2",
+            (EepromLayout::Mlx90614, 0x1F) => "Id3",
+            (EepromLayout::Mlx90615, 0x00) => "Address",
+            (EepromLayout::Mlx90615, 0x01) => "PwmRange",
+            (EepromLayout::Mlx90615, 0x02) => "Config",
+            (EepromLayout::Mlx90615, 0x03) => "Emissivity",
+            (EepromLayout::Mlx90615, 0x0E) => "Id0",
+            (EepromLayout::Mlx90615, 0x0F) => "Id1",
+            _ => "Factory",
+        };
+        Some(name)
+    }
+
+    /// Whether customers may write the cell at the given address
+    pub fn is_customer_writable(self, address: u8) -> bool {
+        let map = match self {
+            EepromLayout::Mlx90614 => mlx90614::CUSTOMER_WRITABLE_EEPROM,
+            EepromLayout::Mlx90615 => mlx90615::CUSTOMER_WRITABLE_EEPROM,
+        };
+        usize::from(address) < self.cell_count() && map & (1 << address) != 0
+    }
+
+    /// Decode the contents of the cell at the given address
+    pub fn decode(self, address: u8, raw: u16) -> Option<CellValue> {
+        self.cell_name(address)?;
+        let value = match (self, address) {
+            (EepromLayout::Mlx90614, 0x00) | (EepromLayout::Mlx90614, 0x01) => {
+                CellValue::PwmTemperature(f32::from(raw) * 0.01 - 273.15)
+            }
+            (EepromLayout::Mlx90614, 0x02) => CellValue::PwmControl {
+                single_mode: raw & 1 != 0,
+                pwm_enabled: raw & (1 << 1) != 0,
+                sda_push_pull: raw & (1 << 2) != 0,
+                thermal_relay: raw & (1 << 3) != 0,
+                repetition: ((raw >> 4) & 0x1F) as u8,
+                period: (raw >> 9) as u8,
+            },
+            (EepromLayout::Mlx90614, 0x03) => CellValue::TaRange {
+                min: raw as u8,
+                max: (raw >> 8) as u8,
+            },
+            (EepromLayout::Mlx90614, 0x04) => CellValue::Emissivity(f32::from(raw) / 65535.0),
+            (EepromLayout::Mlx90614, 0x05) => CellValue::Config1 {
+                iir: (raw & 0b111) as u8,
+                fir: 8 << ((raw >> 8) & 0b111),
+                gain: Variant::from_config(raw).gain.unwrap_or(1.0),
+                dual_zone: raw & (1 << 6) != 0,
+            },
+            (EepromLayout::Mlx90614, 0x0E) | (EepromLayout::Mlx90615, 0x00) => {
+                CellValue::Address((raw & 0x7F) as u8)
+            }
+            (EepromLayout::Mlx90614, 0x0F) => CellValue::EmissivityCalibration(raw),
+            (EepromLayout::Mlx90614, 0x1C..=0x1F) | (EepromLayout::Mlx90615, 0x0E..=0x0F) => {
+                CellValue::Id(raw)
+            }
+            (EepromLayout::Mlx90615, 0x01) => CellValue::PwmRange(raw),
+            (EepromLayout::Mlx90615, 0x02) => CellValue::Config {
+                iir: ((raw >> 12) & 0b111) as u8,
+            },
+            (EepromLayout::Mlx90615, 0x03) => CellValue::Emissivity(f32::from(raw) / 16384.0),
+            _ => CellValue::Factory(raw),
+        };
+        Some(value)
+    }
+}
+
+/// Decoded contents of an EEPROM cell
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellValue {
+    /// PWM object temperature limit in celsius degrees
+    PwmTemperature(f32),
+    /// MLX90614 PWM control
+    PwmControl {
+        /// Single (true) or extended (false) PWM mode
+        single_mode: bool,
+        /// PWM output enabled instead of SDA
+        pwm_enabled: bool,
+        /// SDA/PWM pin push-pull (true) or open drain (false)
+        sda_push_pull: bool,
+        /// Thermal relay mode
+        thermal_relay: bool,
+        /// PWM repetition number
+        repetition: u8,
+        /// PWM period setting
+        period: u8,
+    },
+    /// MLX90614 ambient temperature range raw bytes
+    TaRange {
+        /// Minimum
+        min: u8,
+        /// Maximum
+        max: u8,
+    },
+    /// MLX90615 PWM temperature range raw value
+    PwmRange(u16),
+    /// Emissivity
+    Emissivity(f32),
+    /// MLX90614 ConfigRegister1
+    Config1 {
+        /// IIR filter setting
+        iir: u8,
+        /// FIR filter length
+        fir: u16,
+        /// Amplifier gain
+        gain: f32,
+        /// Dual-zone thermopile
+        dual_zone: bool,
+    },
+    /// MLX90615 configuration
+    Config {
+        /// IIR filter setting
+        iir: u8,
+    },
+    /// SMBus address
+    Address(u8),
+    /// Factory-calibrated MLX90614 emissivity correction
+    EmissivityCalibration(u16),
+    /// ID number word
+    Id(u16),
+    /// Factory calibration
+    Factory(u16),
+}
+
+/// Contents of the whole EEPROM of a device
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "structured::Image", into = "structured::Image")
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EepromImage {
+    layout: EepromLayout,
+    cells: [u16; MAX_CELLS],
+}
+
+impl EepromImage {
+    /// Create an image from the cell contents
+    ///
+    /// Returns `None` if the number of cells does not match the layout.
+    pub fn new(layout: EepromLayout, cells: &[u16]) -> Option<Self> {
+        if cells.len() != layout.cell_count() {
+            return None;
+        }
+        let mut image = EepromImage {
+            layout,
+            cells: [0; MAX_CELLS],
+        };
+        image.cells[..cells.len()].copy_from_slice(cells);
+        Some(image)
+    }
+
+    /// EEPROM layout
+    pub fn layout(&self) -> EepromLayout {
+        self.layout
+    }
+
+    /// Cell contents
+    pub fn cells(&self) -> &[u16] {
+        &self.cells[..self.layout.cell_count()]
+    }
+
+    /// Contents of the cell at the given address
+    pub fn cell(&self, address: u8) -> Option<u16> {
+        self.cells().get(usize::from(address)).copied()
+    }
+
+    /// Decoded contents of the cell at the given address
+    pub fn decode(&self, address: u8) -> Option<CellValue> {
+        self.layout.decode(address, self.cell(address)?)
+    }
+
+    /// Device ID stored in the image
+    ///
+    /// See `device_id()`.
+    pub fn device_id(&self) -> u64 {
+        let id = match self.layout {
+            EepromLayout::Mlx90614 => &self.cells[0x1C..0x20],
+            EepromLayout::Mlx90615 => &self.cells[0x0E..0x10],
+        };
+        id.iter().fold(0, |id, word| (id << 16) | u64::from(*word))
+    }
+}
+
+/// Kind of EEPROM cell
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellClass {
+    /// Customers may write the cell
+    CustomerWritable,
+    /// Factory calibration or ID
+    Factory,
+}
+
+/// Difference in one EEPROM cell between two images
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellDiff {
+    /// Cell address
+    pub address: u8,
+    /// Cell name
+    pub name: &'static str,
+    /// Kind of cell
+    pub class: CellClass,
+    /// Raw contents in the first image
+    pub raw_a: u16,
+    /// Raw contents in the second image
+    pub raw_b: u16,
+    /// Decoded contents in the first image
+    pub a: CellValue,
+    /// Decoded contents in the second image
+    pub b: CellValue,
+}
+
+impl CellDiff {
+    /// Bits which differ
+    pub fn changed_bits(&self) -> u16 {
+        self.raw_a ^ self.raw_b
+    }
+}
+
+impl fmt::Display for CellDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = match self.class {
+            CellClass::CustomerWritable => "customer-writable",
+            CellClass::Factory => "factory",
+        };
+        write!(
+            f,
+            "0x{:02x} {} ({}): 0x{:04x} -> 0x{:04x}, {:?} -> {:?}",
+            self.address, self.name, class, self.raw_a, self.raw_b, self.a, self.b
+        )
+    }
+}
+
+/// Iterator over the differing cells of two EEPROM images
+///
+/// See `diff()`.
+#[derive(Debug, Clone)]
+pub struct EepromDiff<'a> {
+    a: &'a EepromImage,
+    b: &'a EepromImage,
+    address: u8,
+}
+
+impl Iterator for EepromDiff<'_> {
+    type Item = CellDiff;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let layout = self.a.layout;
+        while usize::from(self.address) < layout.cell_count() {
+            let address = self.address;
+            self.address += 1;
+            let (raw_a, raw_b) = (
+                self.a.cells[address as usize],
+                self.b.cells[address as usize],
+            );
+            if raw_a == raw_b {
+                continue;
+            }
+            let class = if layout.is_customer_writable(address) {
+                CellClass::CustomerWritable
+            } else {
+                CellClass::Factory
+            };
+            return Some(CellDiff {
+                address,
+                name: layout.cell_name(address)?,
+                class,
+                raw_a,
+                raw_b,
+                a: layout.decode(address, raw_a)?,
+                b: layout.decode(address, raw_b)?,
+            });
+        }
+        None
+    }
+}
+
+/// Compare two EEPROM images cell by cell
+///
+/// Returns `None` if the images have different layouts.
+pub fn diff<'a>(a: &'a EepromImage, b: &'a EepromImage) -> Option<EepromDiff<'a>> {
+    if a.layout != b.layout {
+        return None;
+    }
+    Some(EepromDiff { a, b, address: 0 })
+}
+
+macro_rules! read_image {
+    ($ic_marker:ident, $ic_reg:ident, $layout:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Read the whole EEPROM
+            pub fn read_eeprom_image(&mut self) -> Result<EepromImage, Error<E>> {
+                let layout = EepromLayout::$layout;
+                let mut cells = [0; MAX_CELLS];
+                for (address, cell) in cells[..layout.cell_count()].iter_mut().enumerate() {
+                    *cell = self.read_u16($ic_reg::EEPROM_COMMAND | address as u8)?;
+                }
+                Ok(EepromImage { layout, cells })
+            }
+        }
+    };
+}
+read_image!(Mlx90614, mlx90614, Mlx90614);
+read_image!(Mlx90615, mlx90615, Mlx90615);
+
+#[cfg(feature = "std")]
+mod intel_hex {
+    use super::{EepromImage, EepromLayout, MAX_CELLS};
+    use std::{fmt, fmt::Write, string::String, vec::Vec};
+
+    /// Bytes per data record
+    const RECORD_LEN: usize = 16;
+
+    /// Intel HEX decoding error
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IntelHexError {
+        /// Malformed record in the given line (starting at 1)
+        Syntax(usize),
+        /// Checksum mismatch in the given line (starting at 1)
+        Checksum(usize),
+        /// The amount of data does not match the EEPROM layout
+        Length,
+    }
+
+    impl fmt::Display for IntelHexError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                IntelHexError::Syntax(line) => write!(f, "malformed record in line {}", line),
+                IntelHexError::Checksum(line) => write!(f, "checksum mismatch in line {}", line),
+                IntelHexError::Length => write!(f, "data does not match the EEPROM layout"),
+            }
+        }
+    }
+
+    impl std::error::Error for IntelHexError {}
+
+    /// Parse a pair of hexadecimal digits
+    fn hex_byte(pair: &[u8]) -> Option<u8> {
+        let nibble = |c: u8| char::from(c).to_digit(16);
+        Some((nibble(pair[0])? << 4 | nibble(pair[1])?) as u8)
+    }
+
+    fn record(out: &mut String, kind: u8, address: u16, data: &[u8]) {
+        let mut bytes = Vec::with_capacity(data.len() + 4);
+        bytes.push(data.len() as u8);
+        bytes.extend_from_slice(&address.to_be_bytes());
+        bytes.push(kind);
+        bytes.extend_from_slice(data);
+        let checksum = bytes
+            .iter()
+            .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+            .wrapping_neg();
+        out.push(':');
+        for byte in bytes.iter().chain(Some(&checksum)) {
+            let _ = write!(out, "{:02X}", byte);
+        }
+        out.push('\n');
+    }
+
+    impl EepromImage {
+        /// Encode the image in Intel HEX format
+        ///
+        /// Each cell is stored little-endian at byte address `2 * cell address`.
+        pub fn to_intel_hex(&self) -> String {
+            let bytes: Vec<u8> = self.cells().iter().flat_map(|c| c.to_le_bytes()).collect();
+            let mut out = String::new();
+            for (i, chunk) in bytes.chunks(RECORD_LEN).enumerate() {
+                record(&mut out, 0x00, (i * RECORD_LEN) as u16, chunk);
+            }
+            record(&mut out, 0x01, 0, &[]);
+            out
+        }
+
+        /// Decode an image with the given layout in Intel HEX format
+        ///
+        /// The data must cover exactly the cells of the layout. Otherwise, e.g. for a
+        /// truncated dump, `IntelHexError::Length` is returned.
+        pub fn from_intel_hex(layout: EepromLayout, hex: &str) -> Result<Self, IntelHexError> {
+            let mut bytes = [None; 2 * MAX_CELLS];
+            for (i, line) in hex.lines().enumerate() {
+                let line_number = i + 1;
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let record = line
+                    .strip_prefix(':')
+                    .filter(|r| r.len() % 2 == 0 && r.len() >= 10)
+                    .ok_or(IntelHexError::Syntax(line_number))?;
+                let record = record
+                    .as_bytes()
+                    .chunks(2)
+                    .map(hex_byte)
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or(IntelHexError::Syntax(line_number))?;
+                let len = usize::from(record[0]);
+                if record.len() != len + 5 {
+                    return Err(IntelHexError::Syntax(line_number));
+                }
+                if record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+                    return Err(IntelHexError::Checksum(line_number));
+                }
+                let address = usize::from(u16::from_be_bytes([record[1], record[2]]));
+                match record[3] {
+                    0x00 => {
+                        let data = &record[4..4 + len];
+                        let slots = bytes
+                            .get_mut(address..address + len)
+                            .ok_or(IntelHexError::Length)?;
+                        for (slot, byte) in slots.iter_mut().zip(data) {
+                            *slot = Some(*byte);
+                        }
+                    }
+                    0x01 => break,
+                    _ => return Err(IntelHexError::Syntax(line_number)),
+                }
+            }
+            let len = 2 * layout.cell_count();
+            if bytes[..len].iter().any(Option::is_none) || bytes[len..].iter().any(Option::is_some)
+            {
+                return Err(IntelHexError::Length);
+            }
+            let mut cells = [0; MAX_CELLS];
+            for (cell, pair) in cells.iter_mut().zip(bytes[..len].chunks(2)) {
+                *cell = u16::from_le_bytes([pair[0].unwrap_or(0), pair[1].unwrap_or(0)]);
+            }
+            Ok(EepromImage { layout, cells })
+        }
+    }
+}
+#[cfg(feature = "std")]
+pub use intel_hex::IntelHexError;
+
+/// Structured representation naming every cell and decoding its fields
+#[cfg(feature = "serde")]
+mod structured {
+    use super::{CellValue, EepromImage, EepromLayout};
+    use std::{fmt, string::String, vec::Vec};
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Image {
+        layout: EepromLayout,
+        device_id: u64,
+        cells: Vec<Cell>,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Cell {
+        address: u8,
+        name: String,
+        raw: u16,
+        value: Option<CellValue>,
+    }
+
+    /// The fields of the image are inconsistent
+    #[derive(Debug)]
+    pub struct InvalidImage(&'static str);
+
+    impl fmt::Display for InvalidImage {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl From<EepromImage> for Image {
+        fn from(image: EepromImage) -> Self {
+            let layout = image.layout();
+            Image {
+                layout,
+                device_id: image.device_id(),
+                cells: image
+                    .cells()
+                    .iter()
+                    .enumerate()
+                    .map(|(address, raw)| Cell {
+                        address: address as u8,
+                        name: layout.cell_name(address as u8).unwrap_or("").into(),
+                        raw: *raw,
+                        value: layout.decode(address as u8, *raw),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl TryFrom<Image> for EepromImage {
+        type Error = InvalidImage;
+
+        fn try_from(image: Image) -> Result<Self, Self::Error> {
+            let layout = image.layout;
+            let mut cells = Vec::with_capacity(image.cells.len());
+            for (address, cell) in image.cells.iter().enumerate() {
+                if usize::from(cell.address) != address {
+                    return Err(InvalidImage("cells do not match the EEPROM layout"));
+                }
+                if layout.cell_name(cell.address) != Some(cell.name.as_str()) {
+                    return Err(InvalidImage("cell name does not match the EEPROM layout"));
+                }
+                if cell.value != layout.decode(cell.address, cell.raw) {
+                    return Err(InvalidImage("cell value does not match its raw contents"));
+                }
+                cells.push(cell.raw);
+            }
+            let eeprom = EepromImage::new(layout, &cells)
+                .ok_or(InvalidImage("cells do not match the EEPROM layout"))?;
+            if eeprom.device_id() != image.device_id {
+                return Err(InvalidImage("device ID does not match the ID cells"));
+            }
+            Ok(eeprom)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Emissivity estimation from a known target temperature
+
+use crate::{ic, radiometry::kelvin_pow4, EmissivityCalibration, Error, Mlx9061x};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Emissivity estimated from a known target temperature
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EmissivityEstimate {
+    /// Emissivity epsilon that reproduces the known target temperature
+    pub emissivity: f32,
+    /// Emissivity configured in the device during the measurement
+    pub current_emissivity: f32,
+    /// Measured ambient temperature in celsius degrees
+    pub ambient: f32,
+    /// Measured object temperature in celsius degrees
+    pub object: f32,
+    /// Raw IR data of the measurement
+    pub raw_ir: i16,
+}
+
+/// Solve for the emissivity that turns the measured object temperature into the known one
+///
+/// The object radiation seen by the sensor is proportional to `epsilon * (To^4 - Ta^4)`.
+fn solve(current_emissivity: f32, ambient: f32, object: f32, known_target: f32) -> Option<f32> {
+    let ta4 = kelvin_pow4(ambient);
+    let known = kelvin_pow4(known_target) - ta4;
+    if known == 0.0 {
+        return None;
+    }
+    let emissivity = current_emissivity * (kelvin_pow4(object) - ta4) / known;
+    if emissivity > 0.0 {
+        Some(emissivity)
+    } else {
+        None
+    }
+}
+
+macro_rules! estimate_emissivity {
+    ($ic_marker:ident, $raw_method:ident, $object_method:ident, $set_result:ty) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Estimate the emissivity of a target at a known temperature in celsius degrees
+            ///
+            /// The raw IR data, ambient and object temperatures are read at the
+            /// currently configured emissivity.
+            ///
+            /// Returns `Error::InvalidInputData` if the emissivity cannot be solved for.
+            /// e.g. if the target is at the ambient temperature.
+            pub fn estimate_emissivity(
+                &mut self,
+                known_target: f32,
+            ) -> Result<EmissivityEstimate, Error<E>> {
+                let current_emissivity = self.emissivity()?;
+                let raw_ir = self.$raw_method()?;
+                let ambient = self.ambient_temperature()?;
+                let object = self.$object_method()?;
+                if raw_ir == 0 {
+                    return Err(Error::InvalidInputData);
+                }
+                let emissivity = solve(current_emissivity, ambient, object, known_target)
+                    .ok_or(Error::InvalidInputData)?;
+                Ok(EmissivityEstimate {
+                    emissivity,
+                    current_emissivity,
+                    ambient,
+                    object,
+                    raw_ir,
+                })
+            }
+
+            /// Estimate the emissivity of a target at a known temperature and program it
+            ///
+            /// See `estimate_emissivity()` and `set_emissivity()`.
+            /// Estimates outside of the range supported by the device will return
+            /// `Error::EmissivityOutOfRange` and nothing will be written.
+            pub fn program_estimated_emissivity<D: DelayNs>(
+                &mut self,
+                known_target: f32,
+                delay: &mut D,
+            ) -> Result<(EmissivityEstimate, $set_result), Error<E>> {
+                let estimate = self.estimate_emissivity(known_target)?;
+                let result = self.set_emissivity(estimate.emissivity, delay)?;
+                Ok((estimate, result))
+            }
+        }
+    };
+}
+estimate_emissivity!(
+    Mlx90614,
+    raw_ir_channel1,
+    object1_temperature,
+    EmissivityCalibration
+);
+estimate_emissivity!(Mlx90615, raw_ir, object_temperature, ());
@@ -6,20 +6,52 @@
 //! This driver allows you to:
 //! - Read the last object temperature measurement. See: [`object1_temperature()`].
 //! - Read the last ambient temperature measurement. See: [`ambient_temperature()`].
+//! - Raise over/under-temperature alarms with hysteresis and debounce. See: [`Alarm`].
+//! - Detect thermal shocks from the ambient temperature slope. See: [`ThermalMonitor`].
+//! - Track running min/max/mean/standard deviation of readings. See: [`RawStats`] and [`RunningStats`].
+//! - Take several samples and reduce them with mean, median or trimmed mean. See: [`Sampler`].
+//! - Read the object temperature together with its specified accuracy. See: [`measure_with_uncertainty()`].
 //! - Read the last raw IR measurement. See: [`raw_ir_channel1()`].
+//! - Read all measurement values back-to-back. See: [`snapshot()`].
 //! - Get/Set the emissivity. See: [`set_emissivity()`].
+//! - Set the emissivity of a common material. See: [`set_emissivity_for()`].
+//! - Estimate the emissivity from a known target temperature. See: [`estimate_emissivity()`].
+//! - Compute the object temperature on the host from raw IR data. See: [`IrModel`].
+//! - Compensate an optical window in front of the sensor. See: [`OpticalWindow`].
+//! - Apply a two-point user calibration. See: [`Calibrated`].
 //! - Get the device ID. See: [`device_id()`].
+//! - Read and write registers not covered by the high-level API. See: [`read_eeprom()`].
+//! - Archive the whole EEPROM as Intel HEX or JSON. See: [`read_eeprom_image()`].
+//! - Compare EEPROM images field by field. See: [`diff()`].
+//! - Identify the MLX90614 part variant. See: [`variant()`].
+//! - Read both zones of a dual-zone MLX90614. See: [`dual_zone_temperature()`].
+//! - Configure PEC checking and query bus error counters. See: [`set_pec_mode()`] and [`bus_stats()`].
 //! - Set the device address. See: [`set_address()`].
+//! - Access a device with an unknown address via the broadcast address. See: [`SlaveAddr::Broadcast`].
 //! - Put the device to sleep. See: [`sleep()`].
 //! - Wake the device from sleep. See: [`wake_mlx90614()`].
+//! - Recover a stuck bus. See: [`recover_bus()`].
+//! - Detect transactions exceeding the SMBus timeout and re-issue reads. See: [`SmbusTimeout`].
+//! - Wait for valid data after power-on or wake. See: [`wait_data_ready()`].
 //!
 //! [`object1_temperature()`]: struct.Mlx9061x.html#method.object1_temperature
 //! [`ambient_temperature()`]: struct.Mlx9061x.html#method.ambient_temperature
+//! [`measure_with_uncertainty()`]: struct.Mlx9061x.html#method.measure_with_uncertainty
 //! [`raw_ir_channel1()`]: struct.Mlx9061x.html#method.raw_ir_channel1
+//! [`snapshot()`]: struct.Mlx9061x.html#method.snapshot
 //! [`set_emissivity()`]: struct.Mlx9061x.html#method.set_emissivity
+//! [`set_emissivity_for()`]: struct.Mlx9061x.html#method.set_emissivity_for
+//! [`estimate_emissivity()`]: struct.Mlx9061x.html#method.estimate_emissivity
 //! [`device_id()`]: struct.Mlx9061x.html#method.device_id
+//! [`read_eeprom()`]: struct.Mlx9061x.html#method.read_eeprom
+//! [`read_eeprom_image()`]: struct.Mlx9061x.html#method.read_eeprom_image
+//! [`variant()`]: struct.Mlx9061x.html#method.variant
+//! [`dual_zone_temperature()`]: struct.Mlx9061x.html#method.dual_zone_temperature
+//! [`set_pec_mode()`]: struct.Mlx9061x.html#method.set_pec_mode
+//! [`bus_stats()`]: struct.Mlx9061x.html#method.bus_stats
 //! [`set_address()`]: struct.Mlx9061x.html#method.set_address
 //! [`sleep()`]: struct.Mlx9061x.html#method.sleep
+//! [`wait_data_ready()`]: struct.Mlx9061x.html#method.wait_data_ready
 //!
 //! <!-- TODO
 //! [Introductory blog post](TODO)
@@ -55,6 +87,17 @@
 //! mlx9061x = { version = "0.3.0", features = ["defmt-03"] }
 //! ```
 //!
+//! ### std and serde
+//!
+//! The feature "`std`" adds Intel HEX encoding and decoding of [`EepromImage`].
+//! The feature "`serde`" additionally implements `Serialize` and `Deserialize` for it
+//! in a structured format naming every cell and decoding its fields.
+//!
+//! ```toml
+//! [dependencies]
+//! mlx9061x = { version = "0.3.0", features = ["serde"] }
+//! ```
+//!
 //! ## Usage examples (see also examples folder)
 //!
 //! To use this driver, import this crate and an `embedded_hal` implementation,
@@ -130,6 +173,7 @@
 //! ### Change the device address
 //!
 //! This change will be permanently stored in the device EEPROM.
+//! The device only answers at the new address after a power cycle.
 //!
 //! ```no_run
 //! use linux_embedded_hal::{I2cdev, Delay};
@@ -139,7 +183,9 @@
 //! let addr = SlaveAddr::default();
 //! let mut sensor = Mlx9061x::new_mlx90614(dev, addr, 5).unwrap();
 //! let mut delay = Delay{};
-//! sensor.set_address(SlaveAddr::Alternative(0x5C), &mut delay).unwrap();
+//! let change = sensor.set_address(SlaveAddr::Alternative(0x5C), &mut delay).unwrap();
+//! // power cycle the device
+//! sensor.confirm_address_change(change);
 //! ```
 //!
 //! ### Put the device to sleep and wake it again
@@ -219,15 +265,59 @@
 // avoid suggestion about inclusive ranges containing floats
 #![allow(clippy::manual_range_contains)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::marker::PhantomData;
 mod mlx90614;
 pub use crate::mlx90614::wake_mlx90614;
 mod mlx90615;
 pub use crate::mlx90615::wake_mlx90615;
 mod types;
-pub use crate::types::{ic, Error, SlaveAddr};
+pub use crate::types::{
+    ic, DualZoneReading, EmissivityCalibration, Error, PendingAddressChange, SlaveAddr,
+};
 mod common;
+pub use crate::common::recover_bus;
+mod accuracy;
+pub use crate::accuracy::{accuracy, AccuracyVariant, Grade, Measurement};
+mod alarm;
+pub use crate::alarm::{Alarm, AlarmConfig, AlarmEvent, AlarmLevel, AlarmState};
+mod calibration;
+pub use crate::calibration::{Calibrated, Calibration, CalibrationPoint};
+mod eeprom;
+#[cfg(feature = "std")]
+pub use crate::eeprom::IntelHexError;
+pub use crate::eeprom::{
+    diff, CellClass, CellDiff, CellValue, EepromDiff, EepromImage, EepromLayout,
+};
+mod emissivity;
+pub use crate::emissivity::EmissivityEstimate;
+mod material;
+pub use crate::material::Material;
+mod model;
+pub use crate::model::{IrModel, IrModelFit, IrSample};
+mod pec;
+pub use crate::pec::{BusStats, PecMode};
+mod radiometry;
 mod register_access;
+pub mod registers;
+pub use crate::registers::{unlock_factory_cells, FactoryCellsUnlocked};
+mod sampler;
+pub use crate::sampler::{Reducer, Sample, Sampler};
+mod settling;
+mod snapshot;
+pub use crate::snapshot::{Snapshot, SnapshotValue};
+mod stats;
+pub use crate::stats::{RawStatistics, RawStats, RunningStats, Statistics};
+mod thermal;
+pub use crate::thermal::{ObjectReading, Stability, ThermalMonitor};
+mod timeout;
+pub use crate::timeout::{Clock, SmbusTimeout, TimeoutError, SMBUS_TIMEOUT_US};
+mod variant;
+pub use crate::variant::{Supply, Variant, Zones};
+mod window;
+pub use crate::window::OpticalWindow;
 
 /// MLX90614/MLX90615 device driver
 #[derive(Debug)]
@@ -236,5 +326,13 @@ pub struct Mlx9061x<I2C, IC> {
     i2c: I2C,
     eeprom_write_delay_ms: u8,
     address: u8,
+    woken_at_ms: Option<u32>,
+    /// Valid data delay cached by `mark_power_on()`
+    data_ready_delay_ms: u32,
+    require_data_ready: bool,
+    /// MLX90614 part variant, if known
+    variant: Option<Variant>,
+    pec_mode: PecMode,
+    bus_stats: BusStats,
     _ic: PhantomData<IC>,
 }
@@ -0,0 +1,71 @@
+//! Emissivity presets for common materials
+
+/// Common materials with well-known emissivity values
+///
+/// The values are typical values found in the literature. The actual emissivity
+/// of a surface depends on its finish, temperature and the viewing angle, so
+/// these are a starting point rather than a substitute for measuring.
+///
+/// Some materials like polished metals have a very low emissivity which is
+/// outside of the range supported by some devices. e.g. the MLX90614 only
+/// supports values from 0.1.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Material {
+    /// Human skin (0.98)
+    HumanSkin,
+    /// Water (0.96)
+    Water,
+    /// Ice (0.97)
+    Ice,
+    /// Matte black paint (0.97)
+    MatteBlackPaint,
+    /// Anodised aluminium (0.77)
+    AnodisedAluminium,
+    /// Polished aluminium (0.05)
+    PolishedAluminium,
+    /// Oxidised steel (0.79)
+    OxidisedSteel,
+    /// Polished stainless steel (0.075)
+    PolishedStainlessSteel,
+    /// Wood (0.90)
+    Wood,
+    /// Concrete (0.92)
+    Concrete,
+    /// Brick (0.93)
+    Brick,
+    /// Glass (0.92)
+    Glass,
+    /// Paper (0.93)
+    Paper,
+    /// Rubber (0.95)
+    Rubber,
+    /// Plastics (0.94)
+    Plastic,
+    /// Textiles (0.95)
+    Textile,
+}
+
+impl Material {
+    /// Typical emissivity epsilon of the material [0.0-1.0]
+    pub fn emissivity(self) -> f32 {
+        match self {
+            Material::HumanSkin => 0.98,
+            Material::Water => 0.96,
+            Material::Ice => 0.97,
+            Material::MatteBlackPaint => 0.97,
+            Material::AnodisedAluminium => 0.77,
+            Material::PolishedAluminium => 0.05,
+            Material::OxidisedSteel => 0.79,
+            Material::PolishedStainlessSteel => 0.075,
+            Material::Wood => 0.90,
+            Material::Concrete => 0.92,
+            Material::Brick => 0.93,
+            Material::Glass => 0.92,
+            Material::Paper => 0.93,
+            Material::Rubber => 0.95,
+            Material::Plastic => 0.94,
+            Material::Textile => 0.95,
+        }
+    }
+}
@@ -3,7 +3,8 @@
 use crate::{
     ic,
     register_access::mlx90614::{self, Register, DEV_ADDR},
-    Error, Mlx9061x, SlaveAddr,
+    BusStats, DualZoneReading, EmissivityCalibration, Error, Material, Mlx9061x, PecMode,
+    SlaveAddr, Zones,
 };
 use core::marker::PhantomData;
 use embedded_hal::{delay::DelayNs, digital::OutputPin, i2c::I2c};
@@ -14,9 +15,10 @@ where
 {
     /// Create new instance of the MLX90614 device.
     ///
-    /// The slave address must match the address stored in the device EEPROM.
+    /// The slave address must match the address stored in the device EEPROM
+    /// unless `SlaveAddr::Broadcast` is used.
     /// To change it you need to connect first and then change it with `set_address()`.
-    /// An invalid alternative slave address will return `Error::InvalidInputData`.
+    /// An invalid alternative slave address will return `Error::InvalidAddress`.
     ///
     /// When writing to the EEPROM waiting a certain amount of time is necessary.
     /// This delay is configured through the `eeprom_write_delay_ms` parameter
@@ -31,13 +33,19 @@ where
             i2c,
             eeprom_write_delay_ms,
             address,
+            woken_at_ms: None,
+            data_ready_delay_ms: 0,
+            require_data_ready: false,
+            variant: None,
+            pec_mode: PecMode::Strict,
+            bus_stats: BusStats::default(),
             _ic: PhantomData,
         })
     }
 
     /// Read the ambient temperature in celsius degrees
     pub fn ambient_temperature(&mut self) -> Result<f32, Error<E>> {
-        let t = self.read_u16(Register::TA)?;
+        let t = self.read_measurement_u16(Register::TA)?;
         let t = f32::from(t) * 0.02 - 273.15;
         Ok(t)
     }
@@ -47,14 +55,16 @@ where
     /// Note ONLY use to avoid floating-point ops, as this gives less accurate
     /// temperature readings compared to using `ambient_temperature()`.
     pub fn ambient_temperature_as_int(&mut self) -> Result<u16, Error<E>> {
-        let t = self.read_u16(Register::TA)?;
+        let t = self.read_measurement_u16(Register::TA)?;
         let t = (t * 2) / 100 - 273;
         Ok(t)
     }
 
     /// Read the object 1 temperature in celsius degrees
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object1_temperature(&mut self) -> Result<f32, Error<E>> {
-        let t = self.read_u16(Register::TOBJ1)?;
+        let t = self.read_object_u16(Register::TOBJ1)?;
         let t = f32::from(t) * 0.02 - 273.15;
         Ok(t)
     }
@@ -63,8 +73,10 @@ where
     ///
     /// Note ONLY use to avoid floating-point ops, as this gives less accurate
     /// temperature readings compared to using `object1_temperature()`.
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object1_temperature_as_int(&mut self) -> Result<u16, Error<E>> {
-        let t = self.read_u16(Register::TOBJ1)?;
+        let t = self.read_object_u16(Register::TOBJ1)?;
         let t = (t * 2) / 100 - 273;
         Ok(t)
     }
@@ -72,8 +84,12 @@ where
     /// Read the object 2 temperature in celsius degrees
     ///
     /// Note that this is only available in dual-zone thermopile device variants.
+    /// Otherwise `Error::NotSupported` will be returned. See `variant()`.
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object2_temperature(&mut self) -> Result<f32, Error<E>> {
-        let t = self.read_u16(Register::TOBJ2)?;
+        self.check_dual_zone()?;
+        let t = self.read_object_u16(Register::TOBJ2)?;
         let t = f32::from(t) * 0.02 - 273.15;
         Ok(t)
     }
@@ -81,11 +97,15 @@ where
     /// Read the object 2 temperature in celsius degrees as u16 value
     ///
     /// Note that this is only available in dual-zone thermopile device variants.
+    /// Otherwise `Error::NotSupported` will be returned. See `variant()`.
     ///
     /// Note ONLY use to avoid floating-point ops, as this gives less accurate
     /// temperature readings compared to using `object2_temperature()`.
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object2_temperature_as_int(&mut self) -> Result<u16, Error<E>> {
-        let t = self.read_u16(Register::TOBJ2)?;
+        self.check_dual_zone()?;
+        let t = self.read_object_u16(Register::TOBJ2)?;
         let t = (t * 2) / 100 - 273;
         Ok(t)
     }
@@ -96,10 +116,34 @@ where
     }
 
     /// Read the channel 2 raw IR data
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    /// Otherwise `Error::NotSupported` will be returned. See `variant()`.
     pub fn raw_ir_channel2(&mut self) -> Result<i16, Error<E>> {
+        self.check_dual_zone()?;
         self.read_i16(Register::RAW_IR2)
     }
 
+    /// Read the object 1 and object 2 temperatures in celsius degrees
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    /// Otherwise `Error::NotSupported` will be returned. See `variant()`.
+    pub fn dual_zone_temperature(&mut self) -> Result<DualZoneReading, Error<E>> {
+        self.check_dual_zone()?;
+        Ok(DualZoneReading {
+            object1: self.object1_temperature()?,
+            object2: self.object2_temperature()?,
+        })
+    }
+
+    pub(crate) fn check_dual_zone(&mut self) -> Result<(), Error<E>> {
+        if self.variant()?.zones == Zones::Dual {
+            Ok(())
+        } else {
+            Err(Error::NotSupported)
+        }
+    }
+
     /// Get emissivity epsilon
     pub fn emissivity(&mut self) -> Result<f32, Error<E>> {
         let raw = self.read_u16(Register::EMISSIVITY)?;
@@ -108,20 +152,83 @@ where
 
     /// Set emissivity epsilon [0.1-1.0]
     ///
-    /// Wrong values will return `Error::InvalidInputData`.
+    /// As documented by Melexis, the factory-calibrated cell 0x0F is rescaled
+    /// together with the emissivity cell (0x04). Otherwise the readings would be wrong.
+    /// Both cells are read back after writing them.
+    ///
+    /// The previous contents of both cells are returned so that the change can be
+    /// undone with `restore_emissivity()`.
+    ///
+    /// If writing or verifying cell 0x0F fails, cell 0x04 is restored to its previous
+    /// value before the error is returned. If restoring it fails as well, the error of
+    /// the restore is returned instead.
+    ///
+    /// Wrong values will return `Error::EmissivityOutOfRange`.
+    /// A read back mismatch will return `Error::EepromVerificationFailed`.
     pub fn set_emissivity<D: DelayNs>(
         &mut self,
         epsilon: f32,
         delay: &mut D,
-    ) -> Result<(), Error<E>> {
+    ) -> Result<EmissivityCalibration, Error<E>> {
         if epsilon < 0.1 || epsilon > 1.0 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::EmissivityOutOfRange);
         }
         let eps = (epsilon * 65535.0 + 0.5) as u16;
         if eps < 6553 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::EmissivityOutOfRange);
+        }
+        let previous = self.emissivity_calibration()?;
+        let calibration = (u32::from(previous.calibration) * u32::from(previous.emissivity)
+            + u32::from(eps) / 2)
+            / u32::from(eps);
+        let calibration = u16::try_from(calibration).map_err(|_| Error::EmissivityOutOfRange)?;
+        self.write_u16_eeprom_verified(Register::EMISSIVITY, eps, delay)?;
+        if let Err(e) = self.write_u16_eeprom_unprotected_verified(
+            Register::EMISSIVITY_CALIBRATION,
+            calibration,
+            delay,
+        ) {
+            self.write_u16_eeprom_verified(Register::EMISSIVITY, previous.emissivity, delay)?;
+            return Err(e);
         }
-        self.write_u16_eeprom(Register::EMISSIVITY, eps, delay)
+        Ok(previous)
+    }
+
+    /// Set the emissivity to the typical value of a material
+    ///
+    /// See `set_emissivity()`. Materials with an emissivity below 0.1
+    /// will return `Error::EmissivityOutOfRange`.
+    pub fn set_emissivity_for<D: DelayNs>(
+        &mut self,
+        material: Material,
+        delay: &mut D,
+    ) -> Result<EmissivityCalibration, Error<E>> {
+        self.set_emissivity(material.emissivity(), delay)
+    }
+
+    /// Get the raw contents of the EEPROM cells involved in an emissivity change
+    pub fn emissivity_calibration(&mut self) -> Result<EmissivityCalibration, Error<E>> {
+        Ok(EmissivityCalibration {
+            emissivity: self.read_u16(Register::EMISSIVITY)?,
+            calibration: self.read_u16(Register::EMISSIVITY_CALIBRATION)?,
+        })
+    }
+
+    /// Restore the emissivity cells to the values returned by `set_emissivity()`
+    ///
+    /// Both cells are read back after writing them.
+    /// A read back mismatch will return `Error::EepromVerificationFailed`.
+    pub fn restore_emissivity<D: DelayNs>(
+        &mut self,
+        previous: EmissivityCalibration,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.write_u16_eeprom_verified(Register::EMISSIVITY, previous.emissivity, delay)?;
+        self.write_u16_eeprom_unprotected_verified(
+            Register::EMISSIVITY_CALIBRATION,
+            previous.calibration,
+            delay,
+        )
     }
 
     /// Get the device ID
@@ -139,6 +246,7 @@ where
 /// Wake device from sleep mode.
 ///
 /// Note that this includes a 33ms delay.
+/// If valid data tracking is used, call `mark_power_on()` on the new driver afterwards.
 pub fn wake_mlx90614<E, SclPin: OutputPin<Error = E>, SdaPin: OutputPin<Error = E>, D: DelayNs>(
     scl: &mut SclPin,
     sda: &mut SdaPin,
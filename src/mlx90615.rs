@@ -1,7 +1,7 @@
 use crate::{
     ic,
     register_access::mlx90615::{self, Register, DEV_ADDR},
-    Error, Mlx9061x, SlaveAddr,
+    BusStats, Error, Material, Mlx9061x, PecMode, SlaveAddr,
 };
 use core::marker::PhantomData;
 use embedded_hal::{delay::DelayNs, digital::OutputPin, i2c::I2c};
@@ -12,9 +12,10 @@ where
 {
     /// Create new instance of the MLX90615 device.
     ///
-    /// The slave address must match the address stored in the device EEPROM.
+    /// The slave address must match the address stored in the device EEPROM
+    /// unless `SlaveAddr::Broadcast` is used.
     /// To change it you need to connect first and then change it with `set_address()`.
-    /// An invalid alternative slave address will return `Error::InvalidInputData`.
+    /// An invalid alternative slave address will return `Error::InvalidAddress`.
     ///
     /// When writing to the EEPROM waiting a certain amount of time is necessary.
     /// This delay is configured through the `eeprom_write_delay_ms` parameter
@@ -29,6 +30,12 @@ where
             i2c,
             eeprom_write_delay_ms,
             address,
+            woken_at_ms: None,
+            data_ready_delay_ms: 0,
+            require_data_ready: false,
+            variant: None,
+            pec_mode: PecMode::Strict,
+            bus_stats: BusStats::default(),
             _ic: PhantomData,
         })
     }
@@ -40,7 +47,7 @@ where
 {
     /// Read the ambient temperature in celsius degrees
     pub fn ambient_temperature(&mut self) -> Result<f32, Error<E>> {
-        let t = self.read_u16(Register::TA)?;
+        let t = self.read_measurement_u16(Register::TA)?;
         let t = f32::from(t) * 0.02 - 273.15;
         Ok(t)
     }
@@ -50,14 +57,16 @@ where
     /// Note ONLY use to avoid floating-point ops, as this gives less accurate
     /// temperature readings compared to using `ambient_temperature()`.
     pub fn ambient_temperature_as_int(&mut self) -> Result<u16, Error<E>> {
-        let t = self.read_u16(Register::TA)?;
+        let t = self.read_measurement_u16(Register::TA)?;
         let t = (t * 2) / 100 - 273;
         Ok(t)
     }
 
     /// Read the object temperature in celsius degrees
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object_temperature(&mut self) -> Result<f32, Error<E>> {
-        let t = self.read_u16(Register::TOBJ)?;
+        let t = self.read_object_u16(Register::TOBJ)?;
         let t = f32::from(t) * 0.02 - 273.15;
         Ok(t)
     }
@@ -66,8 +75,10 @@ where
     ///
     /// Note ONLY use to avoid floating-point ops, as this gives less accurate
     /// temperature readings compared to using `object_temperature()`.
+    ///
+    /// A measurement flagged as erroneous by the device will return `Error::MeasurementError`.
     pub fn object_temperature_as_int(&mut self) -> Result<u16, Error<E>> {
-        let t = self.read_u16(Register::TOBJ)?;
+        let t = self.read_object_u16(Register::TOBJ)?;
         let t = (t * 2) / 100 - 273;
         Ok(t)
     }
@@ -85,19 +96,30 @@ where
 
     /// Set emissivity epsilon [0.0-1.0]
     ///
-    /// Wrong values will return `Error::InvalidInputData`.
+    /// Wrong values will return `Error::EmissivityOutOfRange`.
     pub fn set_emissivity<D: DelayNs>(
         &mut self,
         epsilon: f32,
         delay: &mut D,
     ) -> Result<(), Error<E>> {
         if epsilon < 0.0 || epsilon > 1.0 {
-            return Err(Error::InvalidInputData);
+            return Err(Error::EmissivityOutOfRange);
         }
         let eps = (epsilon * 16384.0 + 0.5) as u16;
         self.write_u16_eeprom(Register::EMISSIVITY, eps, delay)
     }
 
+    /// Set the emissivity to the typical value of a material
+    ///
+    /// See `set_emissivity()`.
+    pub fn set_emissivity_for<D: DelayNs>(
+        &mut self,
+        material: Material,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.set_emissivity(material.emissivity(), delay)
+    }
+
     /// Get the device ID
     pub fn device_id(&mut self) -> Result<u32, Error<E>> {
         let id0 = self.read_u16(Register::ID0)?;
@@ -109,6 +131,7 @@ where
 /// Wake device from sleep mode.
 ///
 /// Note that this includes a 39ms delay.
+/// If valid data tracking is used, call `mark_power_on()` on the new driver afterwards.
 pub fn wake_mlx90615<E, P: OutputPin<Error = E>, D: DelayNs>(
     scl: &mut P,
     delay: &mut D,
@@ -0,0 +1,146 @@
+//! Host-side object temperature computation from raw IR data
+
+use crate::{
+    ic,
+    radiometry::{celsius_from_pow4, kelvin_pow4},
+    Error, Mlx9061x,
+};
+use embedded_hal::i2c::I2c;
+
+/// Simultaneous raw IR data, ambient and object temperature readings
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IrSample {
+    /// Raw IR data
+    pub raw_ir: i16,
+    /// Ambient temperature in celsius degrees
+    pub ambient: f32,
+    /// Object temperature computed by the device in celsius degrees
+    pub object: f32,
+}
+
+/// Model converting raw IR data and ambient temperature into object temperature
+///
+/// The object temperature is computed following the Stefan-Boltzmann law as
+/// `To^4 = Ta^4 + raw_ir * sensitivity / emissivity` with temperatures in kelvin.
+///
+/// The sensitivity can be supplied or fitted from samples taken from the device
+/// with `IrModelFit`.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IrModel {
+    sensitivity: f32,
+    emissivity: f32,
+}
+
+impl IrModel {
+    /// Create a model from a sensitivity and an emissivity ]0.0-1.0]
+    ///
+    /// Returns `None` if the emissivity is out of range.
+    pub fn new(sensitivity: f32, emissivity: f32) -> Option<Self> {
+        if emissivity <= 0.0 || emissivity > 1.0 {
+            return None;
+        }
+        Some(IrModel {
+            sensitivity,
+            emissivity,
+        })
+    }
+
+    /// Get the same model with a different emissivity ]0.0-1.0]
+    ///
+    /// Returns `None` if the emissivity is out of range.
+    pub fn with_emissivity(self, emissivity: f32) -> Option<Self> {
+        IrModel::new(self.sensitivity, emissivity)
+    }
+
+    /// Get the sensitivity
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
+    /// Get the emissivity
+    pub fn emissivity(&self) -> f32 {
+        self.emissivity
+    }
+
+    /// Compute the object temperature in celsius degrees
+    ///
+    /// The raw IR data is taken as `f32` so that filtered values can be used.
+    pub fn object_temperature(&self, raw_ir: f32, ambient: f32) -> f32 {
+        celsius_from_pow4(kelvin_pow4(ambient) + raw_ir * self.sensitivity / self.emissivity)
+    }
+}
+
+/// Least-squares fit of an `IrModel` to samples taken from the device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IrModelFit {
+    emissivity: f32,
+    sum_xy: f32,
+    sum_xx: f32,
+}
+
+impl IrModelFit {
+    /// Start a fit for samples taken with the given emissivity configured in the device
+    pub fn new(emissivity: f32) -> Self {
+        IrModelFit {
+            emissivity,
+            sum_xy: 0.0,
+            sum_xx: 0.0,
+        }
+    }
+
+    /// Add a sample
+    pub fn add_sample(&mut self, sample: &IrSample) {
+        let raw_ir = f32::from(sample.raw_ir);
+        let radiation =
+            (kelvin_pow4(sample.object) - kelvin_pow4(sample.ambient)) * self.emissivity;
+        self.sum_xy += raw_ir * radiation;
+        self.sum_xx += raw_ir * raw_ir;
+    }
+
+    /// Compute the fitted model
+    ///
+    /// Returns `None` if no samples with a raw IR signal were added or
+    /// the emissivity is out of range.
+    pub fn model(&self) -> Option<IrModel> {
+        if self.sum_xx == 0.0 {
+            return None;
+        }
+        IrModel::new(self.sum_xy / self.sum_xx, self.emissivity)
+    }
+}
+
+macro_rules! ir_sample {
+    ($ic_marker:ident, $( $(#[$attr:meta])* $method:ident: $raw_method:ident, $object_method:ident );*) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            $(
+                $(#[$attr])*
+                pub fn $method(&mut self) -> Result<IrSample, Error<E>> {
+                    Ok(IrSample {
+                        raw_ir: self.$raw_method()?,
+                        ambient: self.ambient_temperature()?,
+                        object: self.$object_method()?,
+                    })
+                }
+            )*
+        }
+    };
+}
+ir_sample!(
+    Mlx90614,
+    /// Read the channel 1 raw IR data together with the ambient and object 1 temperatures
+    ir_sample_channel1: raw_ir_channel1, object1_temperature;
+    /// Read the channel 2 raw IR data together with the ambient and object 2 temperatures
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    ir_sample_channel2: raw_ir_channel2, object2_temperature
+);
+ir_sample!(
+    Mlx90615,
+    /// Read the raw IR data together with the ambient and object temperatures
+    ir_sample: raw_ir, object_temperature
+);
@@ -0,0 +1,58 @@
+//! Packet error code (PEC) checking policy and bus error statistics
+
+use crate::Mlx9061x;
+
+/// Packet error code (PEC) checking policy for reads
+///
+/// Writes always include the PEC since the devices reject writes without it.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PecMode {
+    /// Return `Error::ChecksumMismatch` on the first mismatch (default)
+    #[default]
+    Strict,
+    /// Repeat the read up to the given number of times on mismatch
+    ///
+    /// `Error::ChecksumMismatch` is returned if all attempts fail.
+    Retry(u8),
+    /// Do not check the PEC
+    ///
+    /// Only intended for debugging with adapters which mangle the last byte.
+    Disabled,
+}
+
+/// Bus error counters
+///
+/// The counters saturate at `u32::MAX`.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BusStats {
+    /// Number of reads with a PEC mismatch, including retried ones
+    pub pec_failures: u32,
+    /// Number of repeated reads after a PEC mismatch
+    pub retries: u32,
+    /// Number of I²C bus errors
+    pub i2c_errors: u32,
+}
+
+impl<I2C, IC> Mlx9061x<I2C, IC> {
+    /// Set the PEC checking policy for reads
+    pub fn set_pec_mode(&mut self, mode: PecMode) {
+        self.pec_mode = mode;
+    }
+
+    /// Get the PEC checking policy for reads
+    pub fn pec_mode(&self) -> PecMode {
+        self.pec_mode
+    }
+
+    /// Get the bus error counters
+    pub fn bus_stats(&self) -> BusStats {
+        self.bus_stats
+    }
+
+    /// Reset the bus error counters
+    pub fn reset_bus_stats(&mut self) {
+        self.bus_stats = BusStats::default();
+    }
+}
@@ -0,0 +1,17 @@
+//! Stefan-Boltzmann helpers shared by the radiometric computations
+
+use libm::{powf, sqrtf};
+
+const KELVIN_OFFSET: f32 = 273.15;
+
+/// Fourth power of a temperature in celsius degrees converted to kelvin
+pub(crate) fn kelvin_pow4(celsius: f32) -> f32 {
+    powf(celsius + KELVIN_OFFSET, 4.0)
+}
+
+/// Temperature in celsius degrees from the fourth power of a temperature in kelvin
+///
+/// Negative values are clamped to absolute zero.
+pub(crate) fn celsius_from_pow4(kelvin_pow4: f32) -> f32 {
+    sqrtf(sqrtf(kelvin_pow4.max(0.0))) - KELVIN_OFFSET
+}
@@ -1,8 +1,8 @@
-use crate::{Error, Mlx9061x, SlaveAddr};
+use crate::{ic, Error, Mlx9061x, PecMode, SlaveAddr};
 use embedded_hal::{delay::DelayNs, i2c::I2c};
 use smbus_pec::pec;
 
-fn msb_lsb_to_sign_magnitude(value: u16) -> i16 {
+pub(crate) fn msb_lsb_to_sign_magnitude(value: u16) -> i16 {
     let sign_bit = value & 0b1000_0000_0000_0000;
 
     let value = (value & 0b0111_1111_1111_1111) as i16;
@@ -15,10 +15,17 @@ fn msb_lsb_to_sign_magnitude(value: u16) -> i16 {
 }
 
 pub mod mlx90614 {
-    const EEPROM_COMMAND: u8 = 0x20;
+    pub const EEPROM_COMMAND: u8 = 0x20;
     pub const SLEEP_COMMAND: u8 = 0xFF;
     pub const WAKE_DELAY_MS: u8 = 33;
+    pub const DATA_READY_DELAY_MS: u32 = 250;
     pub const DEV_ADDR: u8 = 0x5A;
+    /// EEPROM cells which customers may write: 0x00-0x05 and 0x0E
+    ///
+    /// The rest holds factory calibration.
+    pub const CUSTOMER_WRITABLE_EEPROM: u32 = 0x0000_403F;
+    /// EEPROM cells which must never be written: ID number 0x1C-0x1F
+    pub const READ_ONLY_EEPROM: u32 = 0xF000_0000;
 
     pub struct Register {}
 
@@ -28,18 +35,31 @@ pub mod mlx90614 {
         pub const TA: u8 = 0x06;
         pub const TOBJ1: u8 = 0x07;
         pub const TOBJ2: u8 = 0x08;
+        pub const TO_MAX: u8 = /*0x00 |*/ EEPROM_COMMAND;
+        pub const TO_MIN: u8 = 0x01 | EEPROM_COMMAND;
+        pub const PWM_CTRL: u8 = 0x02 | EEPROM_COMMAND;
+        pub const TA_RANGE: u8 = 0x03 | EEPROM_COMMAND;
         pub const EMISSIVITY: u8 = 0x04 | EEPROM_COMMAND;
+        pub const CONFIG1: u8 = 0x05 | EEPROM_COMMAND;
         pub const ADDRESS: u8 = 0x0E | EEPROM_COMMAND;
+        pub const EMISSIVITY_CALIBRATION: u8 = 0x0F | EEPROM_COMMAND;
         pub const ID0: u8 = 0x1C | EEPROM_COMMAND;
     }
 }
 
 pub mod mlx90615 {
-    const EEPROM_COMMAND: u8 = 0x10;
-    const RAM_COMMAND: u8 = 0x20;
+    pub const EEPROM_COMMAND: u8 = 0x10;
+    pub const RAM_COMMAND: u8 = 0x20;
     pub const SLEEP_COMMAND: u8 = 0xC6;
     pub const WAKE_DELAY_MS: u8 = 39;
+    pub const DATA_READY_DELAY_MS: u32 = 300;
     pub const DEV_ADDR: u8 = 0x5B;
+    /// EEPROM cells which customers may write: 0x00-0x03
+    ///
+    /// The rest holds factory calibration.
+    pub const CUSTOMER_WRITABLE_EEPROM: u32 = 0x0000_000F;
+    /// EEPROM cells which must never be written: ID number 0x0E-0x0F
+    pub const READ_ONLY_EEPROM: u32 = 0x0000_C000;
 
     pub struct Register {}
 
@@ -48,80 +68,238 @@ pub mod mlx90615 {
         pub const TA: u8 = 0x06 | RAM_COMMAND;
         pub const TOBJ: u8 = 0x07 | RAM_COMMAND;
         pub const ADDRESS: u8 = /*0x00 |*/ EEPROM_COMMAND;
+        pub const PWM_RANGE: u8 = 0x01 | EEPROM_COMMAND;
+        pub const CONFIG: u8 = 0x02 | EEPROM_COMMAND;
         pub const EMISSIVITY: u8 = 0x03 | EEPROM_COMMAND;
         pub const ID0: u8 = 0x0E | EEPROM_COMMAND;
     }
 }
 
+/// EEPROM protection map of an IC
+pub trait EepromMap {
+    /// EEPROM access command
+    const EEPROM_COMMAND: u8;
+    /// Mask of the cell address in the command
+    const CELL_MASK: u8;
+    /// Cells which customers may write
+    const CUSTOMER_WRITABLE: u32;
+    /// Cells which cannot be written at all
+    const READ_ONLY: u32;
+}
+
+impl EepromMap for ic::Mlx90614 {
+    const EEPROM_COMMAND: u8 = mlx90614::EEPROM_COMMAND;
+    const CELL_MASK: u8 = 0x1F;
+    const CUSTOMER_WRITABLE: u32 = mlx90614::CUSTOMER_WRITABLE_EEPROM;
+    const READ_ONLY: u32 = mlx90614::READ_ONLY_EEPROM;
+}
+
+impl EepromMap for ic::Mlx90615 {
+    const EEPROM_COMMAND: u8 = mlx90615::EEPROM_COMMAND;
+    const CELL_MASK: u8 = 0x0F;
+    const CUSTOMER_WRITABLE: u32 = mlx90615::CUSTOMER_WRITABLE_EEPROM;
+    const READ_ONLY: u32 = mlx90615::READ_ONLY_EEPROM;
+}
+
 impl<E, I2C, IC> Mlx9061x<I2C, IC>
 where
     I2C: I2c<Error = E>,
 {
     pub(crate) fn read_u16(&mut self, register: u8) -> Result<u16, Error<E>> {
+        let mut retries = match self.pec_mode {
+            PecMode::Retry(retries) => retries,
+            _ => 0,
+        };
+        loop {
+            match self.read_u16_once(register) {
+                Err(Error::ChecksumMismatch { .. }) if retries > 0 => {
+                    retries -= 1;
+                    self.bus_stats.retries = self.bus_stats.retries.saturating_add(1);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn read_u16_once(&mut self, register: u8) -> Result<u16, Error<E>> {
         let mut data = [0; 3];
-        self.i2c
-            .write_read(self.address, &[register], &mut data)
-            .map_err(Error::I2C)?;
-        let pec = data[2];
-        Self::check_pec(
-            &[
-                self.address << 1,
+        let result = self.i2c.write_read(self.address, &[register], &mut data);
+        self.count_i2c_error(result)?;
+        if self.pec_mode != PecMode::Disabled {
+            let result = Self::check_pec(
                 register,
-                (self.address << 1) + 1,
-                data[0],
-                data[1],
-            ],
-            pec,
-        )?;
+                &[
+                    self.address << 1,
+                    register,
+                    (self.address << 1) + 1,
+                    data[0],
+                    data[1],
+                ],
+                data[2],
+            );
+            if result.is_err() {
+                self.bus_stats.pec_failures = self.bus_stats.pec_failures.saturating_add(1);
+            }
+            result?;
+        }
         Ok(u16::from(data[0]) | (u16::from(data[1]) << 8))
     }
 
+    pub(crate) fn read_measurement_u16(&mut self, register: u8) -> Result<u16, Error<E>> {
+        self.check_data_ready()?;
+        self.read_u16(register)
+    }
+
+    pub(crate) fn read_object_u16(&mut self, register: u8) -> Result<u16, Error<E>> {
+        let value = self.read_measurement_u16(register)?;
+        if value & 0b1000_0000_0000_0000 != 0 {
+            return Err(Error::MeasurementError);
+        }
+        Ok(value)
+    }
+
     pub(crate) fn read_i16(&mut self, register: u8) -> Result<i16, Error<E>> {
-        let value = self.read_u16(register)?;
+        let value = self.read_measurement_u16(register)?;
         Ok(msb_lsb_to_sign_magnitude(value))
     }
 
     pub(crate) fn write_u8(&mut self, command: u8) -> Result<(), Error<E>> {
         let pec = pec(&[self.address << 1, command]);
-        self.i2c
-            .write(self.address, &[command, pec])
-            .map_err(Error::I2C)
+        let result = self.i2c.write(self.address, &[command, pec]);
+        self.count_i2c_error(result)
     }
 
     pub(crate) fn write_u16(&mut self, command: u8, data: u16) -> Result<(), Error<E>> {
         let low = data as u8;
         let high = (data >> 8) as u8;
         let pec = pec(&[self.address << 1, command, low, high]);
-        self.i2c
-            .write(self.address, &[command, low, high, pec])
-            .map_err(Error::I2C)
+        let result = self.i2c.write(self.address, &[command, low, high, pec]);
+        self.count_i2c_error(result)
     }
 
+    pub(crate) fn check_data_ready(&self) -> Result<(), Error<E>> {
+        if self.require_data_ready && self.woken_at_ms.is_some() {
+            Err(Error::DataNotReady)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn count_i2c_error<T>(&mut self, result: Result<T, E>) -> Result<T, Error<E>> {
+        if result.is_err() {
+            self.bus_stats.i2c_errors = self.bus_stats.i2c_errors.saturating_add(1);
+        }
+        result.map_err(Error::I2C)
+    }
+
+    pub(crate) fn check_pec(register: u8, data: &[u8], received: u8) -> Result<(), Error<E>> {
+        let expected = pec(data);
+        if expected != received {
+            Err(Error::ChecksumMismatch {
+                register,
+                expected,
+                received,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn get_address(address: SlaveAddr, default: u8) -> Result<u8, Error<E>> {
+        match address {
+            SlaveAddr::Default => Ok(default),
+            SlaveAddr::Broadcast => Ok(0x00),
+            SlaveAddr::Alternative(a) if a < 0x08 || a > 0x77 => Err(Error::InvalidAddress(a)),
+            SlaveAddr::Alternative(a) => Ok(a),
+        }
+    }
+}
+
+impl<E, I2C, IC> Mlx9061x<I2C, IC>
+where
+    I2C: I2c<Error = E>,
+    IC: EepromMap,
+{
+    /// Write an EEPROM cell which customers may write
+    ///
+    /// Other cells will return `Error::ProtectedCell`.
     pub(crate) fn write_u16_eeprom<D: DelayNs>(
         &mut self,
         command: u8,
         data: u16,
         delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        Self::check_eeprom_writable(command, false)?;
+        self.write_u16_eeprom_cell(command, data, delay)
+    }
+
+    /// Write an EEPROM cell including factory-calibrated ones
+    ///
+    /// Only for procedures documented by Melexis. Read-only cells will
+    /// still return `Error::ProtectedCell`.
+    pub(crate) fn write_u16_eeprom_unprotected<D: DelayNs>(
+        &mut self,
+        command: u8,
+        data: u16,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        Self::check_eeprom_writable(command, true)?;
+        self.write_u16_eeprom_cell(command, data, delay)
+    }
+
+    pub(crate) fn write_u16_eeprom_verified<D: DelayNs>(
+        &mut self,
+        command: u8,
+        data: u16,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.write_u16_eeprom(command, data, delay)?;
+        self.verify_eeprom(command, data, delay)
+    }
+
+    pub(crate) fn write_u16_eeprom_unprotected_verified<D: DelayNs>(
+        &mut self,
+        command: u8,
+        data: u16,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.write_u16_eeprom_unprotected(command, data, delay)?;
+        self.verify_eeprom(command, data, delay)
+    }
+
+    fn write_u16_eeprom_cell<D: DelayNs>(
+        &mut self,
+        command: u8,
+        data: u16,
+        delay: &mut D,
     ) -> Result<(), Error<E>> {
         self.write_u16(command, 0)?;
         delay.delay_ms(u32::from(self.eeprom_write_delay_ms));
         self.write_u16(command, data)
     }
 
-    pub(crate) fn check_pec(data: &[u8], expected: u8) -> Result<(), Error<E>> {
-        if pec(data) != expected {
-            Err(Error::ChecksumMismatch)
-        } else {
-            Ok(())
+    fn verify_eeprom<D: DelayNs>(
+        &mut self,
+        command: u8,
+        data: u16,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        delay.delay_ms(u32::from(self.eeprom_write_delay_ms));
+        if self.read_u16(command)? != data {
+            return Err(Error::EepromVerificationFailed);
         }
+        Ok(())
     }
 
-    pub(crate) fn get_address(address: SlaveAddr, default: u8) -> Result<u8, Error<E>> {
-        match address {
-            SlaveAddr::Default => Ok(default),
-            SlaveAddr::Alternative(0) => Err(Error::InvalidInputData),
-            SlaveAddr::Alternative(a) if a > 127 => Err(Error::InvalidInputData),
-            SlaveAddr::Alternative(a) => Ok(a),
+    fn check_eeprom_writable(command: u8, allow_factory: bool) -> Result<(), Error<E>> {
+        let cell = 1u32 << (command & IC::CELL_MASK);
+        let protected = command & !IC::CELL_MASK != IC::EEPROM_COMMAND
+            || IC::READ_ONLY & cell != 0
+            || (!allow_factory && IC::CUSTOMER_WRITABLE & cell == 0);
+        if protected {
+            Err(Error::ProtectedCell(command))
+        } else {
+            Ok(())
         }
     }
 }
@@ -0,0 +1,239 @@
+//! Typed low-level register access
+//!
+//! This allows reaching registers which are not covered by the high-level API.
+
+use crate::{ic, Error, Mlx9061x};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Permission to write factory-calibrated EEPROM cells
+///
+/// See `unlock_factory_cells()` and `write_eeprom_unprotected()`.
+#[derive(Debug)]
+pub struct FactoryCellsUnlocked {
+    _private: (),
+}
+
+/// Get the permission to write factory-calibrated EEPROM cells
+///
+/// Overwriting the factory calibration permanently ruins the sensor unless the
+/// original values are restored. Only use this for procedures documented by Melexis.
+pub fn unlock_factory_cells() -> FactoryCellsUnlocked {
+    FactoryCellsUnlocked { _private: () }
+}
+
+/// MLX90614 registers
+pub mod mlx90614 {
+    use crate::register_access::mlx90614::{
+        Register, CUSTOMER_WRITABLE_EEPROM, EEPROM_COMMAND, READ_ONLY_EEPROM,
+    };
+
+    /// MLX90614 RAM register
+    #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RamRegister {
+        /// Channel 1 raw IR data
+        RawIr1,
+        /// Channel 2 raw IR data
+        RawIr2,
+        /// Ambient temperature
+        Ta,
+        /// Object 1 temperature
+        Tobj1,
+        /// Object 2 temperature
+        Tobj2,
+    }
+
+    impl RamRegister {
+        /// SMBus command
+        pub fn command(self) -> u8 {
+            match self {
+                RamRegister::RawIr1 => Register::RAW_IR1,
+                RamRegister::RawIr2 => Register::RAW_IR2,
+                RamRegister::Ta => Register::TA,
+                RamRegister::Tobj1 => Register::TOBJ1,
+                RamRegister::Tobj2 => Register::TOBJ2,
+            }
+        }
+    }
+
+    /// MLX90614 EEPROM cell
+    #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EepromRegister {
+        /// PWM maximum object temperature
+        ToMax,
+        /// PWM minimum object temperature
+        ToMin,
+        /// PWM control
+        PwmCtrl,
+        /// Ambient temperature range
+        TaRange,
+        /// Emissivity
+        Emissivity,
+        /// ConfigRegister1
+        Config1,
+        /// SMBus address
+        Address,
+        /// Factory-calibrated emissivity correction (0x0F)
+        EmissivityCalibration,
+        /// ID number word 0-3
+        Id(u8),
+        /// Other factory calibration cell at the given EEPROM address
+        Factory(u8),
+    }
+
+    impl EepromRegister {
+        /// SMBus command
+        ///
+        /// Out of range `Id` and `Factory` addresses wrap around.
+        pub fn command(self) -> u8 {
+            match self {
+                EepromRegister::ToMax => Register::TO_MAX,
+                EepromRegister::ToMin => Register::TO_MIN,
+                EepromRegister::PwmCtrl => Register::PWM_CTRL,
+                EepromRegister::TaRange => Register::TA_RANGE,
+                EepromRegister::Emissivity => Register::EMISSIVITY,
+                EepromRegister::Config1 => Register::CONFIG1,
+                EepromRegister::Address => Register::ADDRESS,
+                EepromRegister::EmissivityCalibration => Register::EMISSIVITY_CALIBRATION,
+                EepromRegister::Id(word) => Register::ID0 + (word & 0b11),
+                EepromRegister::Factory(address) => EEPROM_COMMAND | (address & 0x1F),
+            }
+        }
+
+        /// Whether customers may write the cell
+        pub fn is_customer_writable(self) -> bool {
+            CUSTOMER_WRITABLE_EEPROM & (1 << (self.command() & !EEPROM_COMMAND)) != 0
+        }
+
+        /// Whether the cell must never be written
+        pub fn is_read_only(self) -> bool {
+            READ_ONLY_EEPROM & (1 << (self.command() & !EEPROM_COMMAND)) != 0
+        }
+    }
+}
+
+/// MLX90615 registers
+pub mod mlx90615 {
+    use crate::register_access::mlx90615::{
+        Register, CUSTOMER_WRITABLE_EEPROM, EEPROM_COMMAND, READ_ONLY_EEPROM,
+    };
+
+    /// MLX90615 RAM register
+    #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RamRegister {
+        /// Raw IR data
+        RawIr,
+        /// Ambient temperature
+        Ta,
+        /// Object temperature
+        Tobj,
+    }
+
+    impl RamRegister {
+        /// SMBus command
+        pub fn command(self) -> u8 {
+            match self {
+                RamRegister::RawIr => Register::RAW_IR,
+                RamRegister::Ta => Register::TA,
+                RamRegister::Tobj => Register::TOBJ,
+            }
+        }
+    }
+
+    /// MLX90615 EEPROM cell
+    #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EepromRegister {
+        /// SMBus address and PWM minimum temperature
+        Address,
+        /// PWM temperature range
+        PwmRange,
+        /// Configuration
+        Config,
+        /// Emissivity
+        Emissivity,
+        /// ID number word 0-1
+        Id(u8),
+        /// Factory calibration cell at the given EEPROM address
+        Factory(u8),
+    }
+
+    impl EepromRegister {
+        /// SMBus command
+        ///
+        /// Out of range `Id` and `Factory` addresses wrap around.
+        pub fn command(self) -> u8 {
+            match self {
+                EepromRegister::Address => Register::ADDRESS,
+                EepromRegister::PwmRange => Register::PWM_RANGE,
+                EepromRegister::Config => Register::CONFIG,
+                EepromRegister::Emissivity => Register::EMISSIVITY,
+                EepromRegister::Id(word) => Register::ID0 + (word & 0b1),
+                EepromRegister::Factory(address) => EEPROM_COMMAND | (address & 0x0F),
+            }
+        }
+
+        /// Whether customers may write the cell
+        pub fn is_customer_writable(self) -> bool {
+            CUSTOMER_WRITABLE_EEPROM & (1 << (self.command() & !EEPROM_COMMAND)) != 0
+        }
+
+        /// Whether the cell must never be written
+        pub fn is_read_only(self) -> bool {
+            READ_ONLY_EEPROM & (1 << (self.command() & !EEPROM_COMMAND)) != 0
+        }
+    }
+}
+
+macro_rules! register_api {
+    ($ic_marker:ident, $ic_reg:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Read a RAM register
+            pub fn read_ram(&mut self, register: $ic_reg::RamRegister) -> Result<u16, Error<E>> {
+                self.read_u16(register.command())
+            }
+
+            /// Read an EEPROM cell
+            pub fn read_eeprom(
+                &mut self,
+                register: $ic_reg::EepromRegister,
+            ) -> Result<u16, Error<E>> {
+                self.read_u16(register.command())
+            }
+
+            /// Write an EEPROM cell
+            ///
+            /// The cell will be first cleared, before the new value is written.
+            /// Cells which customers may not write will return `Error::ProtectedCell`.
+            pub fn write_eeprom<D: DelayNs>(
+                &mut self,
+                register: $ic_reg::EepromRegister,
+                value: u16,
+                delay: &mut D,
+            ) -> Result<(), Error<E>> {
+                self.write_u16_eeprom(register.command(), value, delay)
+            }
+
+            /// Write an EEPROM cell including factory-calibrated ones
+            ///
+            /// Only for service procedures documented by Melexis. See `unlock_factory_cells()`.
+            /// Read-only cells will still return `Error::ProtectedCell`.
+            pub fn write_eeprom_unprotected<D: DelayNs>(
+                &mut self,
+                register: $ic_reg::EepromRegister,
+                value: u16,
+                _unlocked: FactoryCellsUnlocked,
+                delay: &mut D,
+            ) -> Result<(), Error<E>> {
+                self.write_u16_eeprom_unprotected(register.command(), value, delay)
+            }
+        }
+    };
+}
+register_api!(Mlx90614, mlx90614);
+register_api!(Mlx90615, mlx90615);
@@ -0,0 +1,185 @@
+//! Repeated sampling with averaging, median and outlier rejection
+
+use crate::{ic, Error, Mlx9061x};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Method used to reduce the collected samples to a single value
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reducer {
+    /// Arithmetic mean of all samples
+    Mean,
+    /// Median of all samples
+    Median,
+    /// Mean after discarding the given number of lowest and highest samples
+    ///
+    /// If too few samples are available, as many samples as possible are discarded
+    /// while keeping at least one (or two, for an even number of samples).
+    TrimmedMean(usize),
+}
+
+/// Reduced value of a sampling run
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// Reduced value
+    pub value: f32,
+    /// Number of valid samples used
+    pub count: usize,
+    /// Difference between the highest and lowest valid samples
+    pub spread: f32,
+}
+
+/// Driver wrapper taking `N` samples per reading and reducing them to a single value
+///
+/// Readings that fail the PEC check or which are flagged as erroneous by the device
+/// are skipped. Any other error is returned immediately.
+#[derive(Debug)]
+pub struct Sampler<I2C, IC, const N: usize> {
+    sensor: Mlx9061x<I2C, IC>,
+    reducer: Reducer,
+    interval_ms: u32,
+    buffer: [f32; N],
+}
+
+impl<I2C, IC, const N: usize> Sampler<I2C, IC, N> {
+    /// Wrap a driver instance
+    ///
+    /// `interval_ms` milliseconds will be waited between consecutive samples.
+    pub fn new(sensor: Mlx9061x<I2C, IC>, reducer: Reducer, interval_ms: u32) -> Self {
+        Sampler {
+            sensor,
+            reducer,
+            interval_ms,
+            buffer: [0.0; N],
+        }
+    }
+
+    /// Destroy the wrapper, return the driver instance
+    pub fn destroy(self) -> Mlx9061x<I2C, IC> {
+        self.sensor
+    }
+
+    /// Get the wrapped driver instance for single readings
+    pub fn sensor_mut(&mut self) -> &mut Mlx9061x<I2C, IC> {
+        &mut self.sensor
+    }
+
+    /// Set the reducer
+    pub fn set_reducer(&mut self, reducer: Reducer) {
+        self.reducer = reducer;
+    }
+
+    /// Set the interval between consecutive samples in milliseconds
+    pub fn set_interval_ms(&mut self, interval_ms: u32) {
+        self.interval_ms = interval_ms;
+    }
+
+    /// Take `N` samples with the provided read function and reduce them
+    ///
+    /// If no valid sample could be taken, the last error is returned.
+    /// Zero samples will return `Error::InvalidInputData`.
+    pub fn sample<E, D, F>(&mut self, delay: &mut D, mut read: F) -> Result<Sample, Error<E>>
+    where
+        D: DelayNs,
+        F: FnMut(&mut Mlx9061x<I2C, IC>) -> Result<f32, Error<E>>,
+    {
+        let mut count = 0;
+        let mut last_error = Error::InvalidInputData;
+        for i in 0..N {
+            if i != 0 {
+                delay.delay_ms(self.interval_ms);
+            }
+            match read(&mut self.sensor) {
+                Ok(value) => {
+                    self.buffer[count] = value;
+                    count += 1;
+                }
+                Err(e @ Error::ChecksumMismatch { .. }) | Err(e @ Error::MeasurementError) => {
+                    last_error = e
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if count == 0 {
+            return Err(last_error);
+        }
+        Ok(reduce(&mut self.buffer[..count], self.reducer))
+    }
+}
+
+fn reduce(samples: &mut [f32], reducer: Reducer) -> Sample {
+    sort(samples);
+    let count = samples.len();
+    let spread = samples[count - 1] - samples[0];
+    let value = match reducer {
+        Reducer::Mean => mean(samples),
+        Reducer::Median => median(samples),
+        Reducer::TrimmedMean(trim) => {
+            let trim = trim.min((count - 1) / 2);
+            mean(&samples[trim..count - trim])
+        }
+    };
+    Sample {
+        value,
+        count,
+        spread,
+    }
+}
+
+fn mean(samples: &[f32]) -> f32 {
+    samples.iter().sum::<f32>() / samples.len() as f32
+}
+
+fn median(sorted: &[f32]) -> f32 {
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        sorted[mid]
+    } else {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    }
+}
+
+fn sort(samples: &mut [f32]) {
+    for i in 1..samples.len() {
+        let mut j = i;
+        while j > 0 && samples[j - 1] > samples[j] {
+            samples.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+macro_rules! sampled_read {
+    ($ic_marker:ident, $( $(#[$attr:meta])* $method:ident ),*) => {
+        impl<E, I2C, const N: usize> Sampler<I2C, ic::$ic_marker, N>
+        where
+            I2C: I2c<Error = E>,
+        {
+            $(
+                $(#[$attr])*
+                pub fn $method<D: DelayNs>(&mut self, delay: &mut D) -> Result<Sample, Error<E>> {
+                    self.sample(delay, |sensor| sensor.$method())
+                }
+            )*
+        }
+    };
+}
+sampled_read!(
+    Mlx90614,
+    /// Sample the ambient temperature in celsius degrees
+    ambient_temperature,
+    /// Sample the object 1 temperature in celsius degrees
+    object1_temperature,
+    /// Sample the object 2 temperature in celsius degrees
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    object2_temperature
+);
+sampled_read!(
+    Mlx90615,
+    /// Sample the ambient temperature in celsius degrees
+    ambient_temperature,
+    /// Sample the object temperature in celsius degrees
+    object_temperature
+);
@@ -0,0 +1,123 @@
+//! Valid data tracking after power-on and wake
+
+use crate::{
+    ic,
+    register_access::{mlx90614, mlx90615},
+    Error, Mlx9061x,
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Estimate the MLX90614 valid data delay from ConfigRegister1
+///
+/// The datasheet delay holds for the default configuration (IIR 100%, FIR 1024).
+/// It is scaled by the FIR filter length and the number of measurement cycles
+/// the IIR filter needs to settle within 5%.
+fn mlx90614_data_ready_delay_ms(config: u16) -> u32 {
+    let iir_cycles = match config & 0b111 {
+        4 => 1,  // 100%
+        5 => 2,  // 80%
+        6 => 3,  // 67%
+        7 => 4,  // 57%
+        0 => 5,  // 50%
+        1 => 11, // 25%
+        2 => 17, // 17%
+        _ => 23, // 13%
+    };
+    let fir = 8 << ((config >> 8) & 0b111);
+    (mlx90614::DATA_READY_DELAY_MS * fir * iir_cycles / 1024).max(1)
+}
+
+/// Estimate the MLX90615 valid data delay from the configuration register
+///
+/// The datasheet delay holds for the default configuration (IIR 100%).
+/// It is scaled by the number of measurement cycles the IIR filter needs
+/// to settle within 5%.
+fn mlx90615_data_ready_delay_ms(config: u16) -> u32 {
+    let iir_cycles = match (config >> 12) & 0b111 {
+        0 | 1 => 1, // 100%
+        2 => 5,     // 50%
+        3 => 8,     // 33%
+        4 => 11,    // 25%
+        5 => 14,    // 20%
+        6 => 17,    // 16.7%
+        _ => 20,    // 14.3%
+    };
+    mlx90615::DATA_READY_DELAY_MS * iir_cycles
+}
+
+impl<I2C, IC> Mlx9061x<I2C, IC> {
+    /// Set whether temperature and raw IR readings require valid data
+    ///
+    /// If enabled, readings after `mark_power_on()` will return `Error::DataNotReady`
+    /// until `is_data_ready()` returns true or `wait_data_ready()` returns.
+    /// This is disabled by default.
+    pub fn set_require_data_ready(&mut self, require: bool) {
+        self.require_data_ready = require;
+    }
+
+    /// Check whether valid data is available at `now_ms`
+    ///
+    /// Returns true if `mark_power_on()` was never called.
+    pub fn is_data_ready(&mut self, now_ms: u32) -> bool {
+        let woken_at_ms = match self.woken_at_ms {
+            Some(woken_at_ms) => woken_at_ms,
+            None => return true,
+        };
+        if now_ms.wrapping_sub(woken_at_ms) >= self.data_ready_delay_ms {
+            self.woken_at_ms = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wait until valid data is available
+    ///
+    /// `now_ms` is the current time, used to wait only the remaining time.
+    pub fn wait_data_ready<D: DelayNs>(&mut self, now_ms: u32, delay: &mut D) {
+        let woken_at_ms = match self.woken_at_ms {
+            Some(woken_at_ms) => woken_at_ms,
+            None => return,
+        };
+        let elapsed_ms = now_ms.wrapping_sub(woken_at_ms);
+        if elapsed_ms < self.data_ready_delay_ms {
+            delay.delay_ms(self.data_ready_delay_ms - elapsed_ms);
+        }
+        self.woken_at_ms = None;
+    }
+}
+
+macro_rules! settling {
+    ($ic_marker:ident, $ic_reg:ident, $delay_fn:ident, $config_reg:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Estimate the time from power-on or wake until valid data is available in milliseconds
+            ///
+            /// This is derived from the filter settings stored in the EEPROM.
+            pub fn data_ready_delay_ms(&mut self) -> Result<u32, Error<E>> {
+                let config = self.read_u16($ic_reg::Register::$config_reg)?;
+                Ok($delay_fn(config))
+            }
+
+            /// Record that the device was powered on or woken up at `timestamp_ms`
+            ///
+            /// The driver cannot detect a power-on or wake by itself, so this must be
+            /// called after each one for the valid data tracking to work. e.g. after
+            /// `wake_mlx90614()`/`wake_mlx90615()` and creating the driver again.
+            /// The filter settings are read from the EEPROM once here to estimate
+            /// the valid data delay. See `data_ready_delay_ms()`.
+            ///
+            /// Until valid data is available, `is_data_ready()` will return false.
+            /// The timestamps are supplied by the caller in milliseconds and may wrap around.
+            pub fn mark_power_on(&mut self, timestamp_ms: u32) -> Result<(), Error<E>> {
+                self.data_ready_delay_ms = self.data_ready_delay_ms()?;
+                self.woken_at_ms = Some(timestamp_ms);
+                Ok(())
+            }
+        }
+    };
+}
+settling!(Mlx90614, mlx90614, mlx90614_data_ready_delay_ms, CONFIG1);
+settling!(Mlx90615, mlx90615, mlx90615_data_ready_delay_ms, CONFIG);
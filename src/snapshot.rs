@@ -0,0 +1,185 @@
+//! Reading all RAM measurement values back-to-back
+
+use crate::{
+    ic,
+    register_access::{self, mlx90614, mlx90615},
+    Error, Mlx9061x, PecMode, Zones,
+};
+use embedded_hal::i2c::I2c;
+
+/// Maximum number of registers read in a snapshot
+const MAX_REGISTERS: usize = 7;
+
+/// RAM register value of a snapshot
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotValue<T> {
+    /// Raw register contents
+    pub raw: u16,
+    /// Converted value
+    pub value: T,
+    /// Whether the PEC matched and, for object temperatures, the error flag is clear
+    pub valid: bool,
+}
+
+/// All RAM measurement values read back-to-back
+///
+/// Temperatures are in celsius degrees.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    /// Ambient temperature
+    pub ambient: SnapshotValue<f32>,
+    /// Object (1) temperature
+    pub object1: SnapshotValue<f32>,
+    /// Object 2 temperature. Only available in dual-zone MLX90614 device variants.
+    pub object2: Option<SnapshotValue<f32>>,
+    /// (Channel 1) raw IR data
+    pub raw_ir1: SnapshotValue<i16>,
+    /// Channel 2 raw IR data. Only available in dual-zone MLX90614 device variants.
+    pub raw_ir2: Option<SnapshotValue<i16>>,
+    /// Whether no measurement update was detected while reading the values
+    ///
+    /// The ambient and object (1) temperatures are read again at the end. If either
+    /// of them changed, the snapshot straddled a measurement update. Consecutive
+    /// measurements often have the same raw values, though, so an update can go
+    /// undetected and `true` does not guarantee that all values come from the
+    /// same measurement cycle.
+    pub consistent: bool,
+}
+
+fn temperature(raw: u16, pec_ok: bool) -> SnapshotValue<f32> {
+    SnapshotValue {
+        raw,
+        value: f32::from(raw) * 0.02 - 273.15,
+        valid: pec_ok,
+    }
+}
+
+fn object_temperature(raw: u16, pec_ok: bool) -> SnapshotValue<f32> {
+    temperature(raw, pec_ok && raw & 0b1000_0000_0000_0000 == 0)
+}
+
+fn raw_ir(raw: u16, pec_ok: bool) -> SnapshotValue<i16> {
+    SnapshotValue {
+        raw,
+        value: register_access::msb_lsb_to_sign_magnitude(raw),
+        valid: pec_ok,
+    }
+}
+
+impl<E, I2C, IC> Mlx9061x<I2C, IC>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read the registers back-to-back with one `write_read` each
+    ///
+    /// Returns the values and whether their PEC matched. PEC mismatches are not retried.
+    fn read_registers(
+        &mut self,
+        registers: &[u8],
+    ) -> Result<[(u16, bool); MAX_REGISTERS], Error<E>> {
+        self.check_data_ready()?;
+        let mut values = [(0, false); MAX_REGISTERS];
+        for (value, register) in values.iter_mut().zip(registers) {
+            let mut data = [0; 3];
+            let result = self.i2c.write_read(self.address, &[*register], &mut data);
+            self.count_i2c_error(result)?;
+            let pec_ok = self.pec_mode == PecMode::Disabled
+                || Self::check_pec(
+                    *register,
+                    &[
+                        self.address << 1,
+                        *register,
+                        (self.address << 1) + 1,
+                        data[0],
+                        data[1],
+                    ],
+                    data[2],
+                )
+                .is_ok();
+            if !pec_ok {
+                self.bus_stats.pec_failures = self.bus_stats.pec_failures.saturating_add(1);
+            }
+            *value = (u16::from(data[0]) | (u16::from(data[1]) << 8), pec_ok);
+        }
+        Ok(values)
+    }
+}
+
+impl<E, I2C> Mlx9061x<I2C, ic::Mlx90614>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read all RAM measurement values back-to-back
+    ///
+    /// The object 2 and channel 2 values are only read in dual-zone device variants.
+    /// See `variant()`. PEC mismatches and measurement error flags are reported
+    /// in the `valid` flags instead of returning an error.
+    pub fn snapshot(&mut self) -> Result<Snapshot, Error<E>> {
+        use mlx90614::Register;
+        if self.variant()?.zones == Zones::Dual {
+            let [ta, ir1, ir2, to1, to2, ta_end, to1_end] = self.read_registers(&[
+                Register::TA,
+                Register::RAW_IR1,
+                Register::RAW_IR2,
+                Register::TOBJ1,
+                Register::TOBJ2,
+                Register::TA,
+                Register::TOBJ1,
+            ])?;
+            Ok(Snapshot {
+                ambient: temperature(ta.0, ta.1),
+                object1: object_temperature(to1.0, to1.1),
+                object2: Some(object_temperature(to2.0, to2.1)),
+                raw_ir1: raw_ir(ir1.0, ir1.1),
+                raw_ir2: Some(raw_ir(ir2.0, ir2.1)),
+                consistent: ta.0 == ta_end.0 && to1.0 == to1_end.0,
+            })
+        } else {
+            let [ta, ir1, to1, ta_end, to1_end, ..] = self.read_registers(&[
+                Register::TA,
+                Register::RAW_IR1,
+                Register::TOBJ1,
+                Register::TA,
+                Register::TOBJ1,
+            ])?;
+            Ok(Snapshot {
+                ambient: temperature(ta.0, ta.1),
+                object1: object_temperature(to1.0, to1.1),
+                object2: None,
+                raw_ir1: raw_ir(ir1.0, ir1.1),
+                raw_ir2: None,
+                consistent: ta.0 == ta_end.0 && to1.0 == to1_end.0,
+            })
+        }
+    }
+}
+
+impl<E, I2C> Mlx9061x<I2C, ic::Mlx90615>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Read all RAM measurement values back-to-back
+    ///
+    /// PEC mismatches and measurement error flags are reported in the `valid`
+    /// flags instead of returning an error.
+    pub fn snapshot(&mut self) -> Result<Snapshot, Error<E>> {
+        use mlx90615::Register;
+        let [ta, ir, to, ta_end, to_end, ..] = self.read_registers(&[
+            Register::TA,
+            Register::RAW_IR,
+            Register::TOBJ,
+            Register::TA,
+            Register::TOBJ,
+        ])?;
+        Ok(Snapshot {
+            ambient: temperature(ta.0, ta.1),
+            object1: object_temperature(to.0, to.1),
+            object2: None,
+            raw_ir1: raw_ir(ir.0, ir.1),
+            raw_ir2: None,
+            consistent: ta.0 == ta_end.0 && to.0 == to_end.0,
+        })
+    }
+}
@@ -0,0 +1,289 @@
+//! Streaming statistics of temperature readings
+
+use crate::{
+    ic,
+    register_access::{mlx90614, mlx90615},
+    Error, Mlx9061x,
+};
+use embedded_hal::i2c::I2c;
+use libm::sqrtf;
+
+/// Running minimum, maximum, mean and standard deviation accumulator for `f32` values
+///
+/// See `RawStats` for an accumulator using only integer arithmetic.
+/// The values are accumulated with Welford's method without storing the samples.
+/// Updating only requires additions, multiplications and a division. The square root
+/// for the standard deviation is only computed when taking a snapshot.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    count: u32,
+    mean: f32,
+    m2: f32,
+    min: f32,
+    max: f32,
+}
+
+/// Snapshot of the statistics accumulated in a `RunningStats`
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statistics {
+    /// Number of accumulated values
+    pub count: u32,
+    /// Minimum value
+    pub min: f32,
+    /// Maximum value
+    pub max: f32,
+    /// Mean value
+    pub mean: f32,
+    /// Sample standard deviation (0.0 for a single value)
+    pub std_dev: f32,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats::new()
+    }
+}
+
+impl RunningStats {
+    /// Create an empty accumulator
+    pub const fn new() -> Self {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: 0.0,
+            max: 0.0,
+        }
+    }
+
+    /// Accumulate a value
+    pub fn update(&mut self, value: f32) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count = self.count.saturating_add(1);
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Get the number of accumulated values
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Get the statistics accumulated so far
+    ///
+    /// Returns `None` if no values have been accumulated.
+    pub fn snapshot(&self) -> Option<Statistics> {
+        if self.count == 0 {
+            return None;
+        }
+        let std_dev = if self.count > 1 {
+            sqrtf(self.m2 / (self.count - 1) as f32)
+        } else {
+            0.0
+        };
+        Some(Statistics {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            std_dev,
+        })
+    }
+
+    /// Get the statistics accumulated so far and reset the accumulator
+    pub fn take(&mut self) -> Option<Statistics> {
+        let snapshot = self.snapshot();
+        self.reset();
+        snapshot
+    }
+
+    /// Reset the accumulator
+    pub fn reset(&mut self) {
+        *self = RunningStats::new();
+    }
+}
+
+/// Running statistics accumulator for raw temperature register values
+///
+/// Updating only requires integer additions and multiplications. The samples are
+/// accumulated relative to the first one so that the sums stay small.
+/// Accumulation stops after `u32::MAX` samples.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawStats {
+    count: u32,
+    offset: u16,
+    sum: i64,
+    sum_sq: u64,
+    min: u16,
+    max: u16,
+}
+
+/// Snapshot of the statistics accumulated in a `RawStats` in raw register units
+///
+/// One raw unit is 0.02 K.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawStatistics {
+    /// Number of accumulated values
+    pub count: u32,
+    /// Minimum value
+    pub min: u16,
+    /// Maximum value
+    pub max: u16,
+    /// Mean value in 1/256 raw units
+    pub mean_q8: u32,
+    /// Sample variance in 1/256 squared raw units (0 for a single value)
+    pub variance_q8: u64,
+}
+
+impl RawStatistics {
+    /// Convert to celsius degrees
+    pub fn to_celsius(&self) -> Statistics {
+        let celsius = |raw: f32| raw * 0.02 - 273.15;
+        Statistics {
+            count: self.count,
+            min: celsius(f32::from(self.min)),
+            max: celsius(f32::from(self.max)),
+            mean: celsius(self.mean_q8 as f32 / 256.0),
+            std_dev: sqrtf(self.variance_q8 as f32 / 256.0) * 0.02,
+        }
+    }
+}
+
+impl Default for RawStats {
+    fn default() -> Self {
+        RawStats::new()
+    }
+}
+
+impl RawStats {
+    /// Create an empty accumulator
+    pub const fn new() -> Self {
+        RawStats {
+            count: 0,
+            offset: 0,
+            sum: 0,
+            sum_sq: 0,
+            min: 0,
+            max: 0,
+        }
+    }
+
+    /// Accumulate a raw value
+    pub fn update(&mut self, raw: u16) {
+        if self.count == u32::MAX {
+            return;
+        }
+        if self.count == 0 {
+            self.offset = raw;
+            self.min = raw;
+            self.max = raw;
+        } else {
+            self.min = self.min.min(raw);
+            self.max = self.max.max(raw);
+        }
+        let delta = i64::from(raw) - i64::from(self.offset);
+        self.sum += delta;
+        self.sum_sq += delta.unsigned_abs() * delta.unsigned_abs();
+        self.count += 1;
+    }
+
+    /// Get the number of accumulated values
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Get the statistics accumulated so far
+    ///
+    /// Returns `None` if no values have been accumulated.
+    pub fn snapshot(&self) -> Option<RawStatistics> {
+        if self.count == 0 {
+            return None;
+        }
+        let n = i128::from(self.count);
+        let sum = i128::from(self.sum);
+        let mean_q8 = ((i128::from(self.offset) * n + sum) * 256 + n / 2) / n;
+        let variance_q8 = if n > 1 {
+            (n * i128::from(self.sum_sq) - sum * sum) * 256 / (n * (n - 1))
+        } else {
+            0
+        };
+        Some(RawStatistics {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean_q8: mean_q8 as u32,
+            variance_q8: variance_q8 as u64,
+        })
+    }
+
+    /// Get the statistics accumulated so far and reset the accumulator
+    pub fn take(&mut self) -> Option<RawStatistics> {
+        let snapshot = self.snapshot();
+        self.reset();
+        snapshot
+    }
+
+    /// Reset the accumulator
+    pub fn reset(&mut self) {
+        *self = RawStats::new();
+    }
+}
+
+macro_rules! tracked_read {
+    ($ic_marker:ident, $( $(#[$attr:meta])* $method:ident: $read:expr ),*) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            $(
+                $(#[$attr])*
+                ///
+                /// Failed readings are not accumulated.
+                pub fn $method(&mut self, stats: &mut RawStats) -> Result<f32, Error<E>> {
+                    let read: fn(&mut Self) -> Result<u16, Error<E>> = $read;
+                    let raw = read(self)?;
+                    stats.update(raw);
+                    Ok(f32::from(raw) * 0.02 - 273.15)
+                }
+            )*
+        }
+    };
+}
+tracked_read!(
+    Mlx90614,
+    /// Read the ambient temperature in celsius degrees and accumulate it
+    track_ambient_temperature:
+    |s| s.read_measurement_u16(mlx90614::Register::TA),
+    /// Read the object 1 temperature in celsius degrees and accumulate it
+    track_object1_temperature:
+    |s| s.read_object_u16(mlx90614::Register::TOBJ1),
+    /// Read the object 2 temperature in celsius degrees and accumulate it
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    /// Otherwise `Error::NotSupported` will be returned. See `variant()`.
+    track_object2_temperature:
+    |s| {
+        s.check_dual_zone()?;
+        s.read_object_u16(mlx90614::Register::TOBJ2)
+    }
+);
+tracked_read!(
+    Mlx90615,
+    /// Read the ambient temperature in celsius degrees and accumulate it
+    track_ambient_temperature:
+    |s| s.read_measurement_u16(mlx90615::Register::TA),
+    /// Read the object temperature in celsius degrees and accumulate it
+    track_object_temperature:
+    |s| s.read_object_u16(mlx90615::Register::TOBJ)
+);
@@ -0,0 +1,179 @@
+//! Thermal shock detection from the ambient temperature slope
+
+use crate::{ic, Error, Mlx9061x};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Thermal stability of the sensor package
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// The ambient temperature slope is within the limit
+    Stable,
+    /// The ambient temperature slope exceeds the limit or is not yet known
+    Unstable,
+}
+
+/// Object temperature reading classified by thermal stability
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObjectReading {
+    /// Object temperature in celsius degrees taken while the sensor was stable
+    Stable(f32),
+    /// Object temperature in celsius degrees taken while the sensor was unstable
+    ///
+    /// The accuracy is degraded due to a thermal gradient in the sensor package.
+    Unstable(f32),
+}
+
+/// Ambient temperature slope monitor
+///
+/// The accuracy of the devices degrades while the sensor package has a thermal
+/// gradient. e.g. right after moving the device into a hot environment.
+/// This tracks the ambient temperature over time (dTa/dt) and reports the
+/// sensor as unstable when the slope exceeds a configurable limit.
+///
+/// The timestamps are supplied by the caller in milliseconds and may wrap around.
+/// At least two ambient temperature readings are necessary to compute a slope.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalMonitor {
+    max_slope: f32,
+    last: Option<(f32, u32)>,
+    slope: Option<f32>,
+}
+
+impl ThermalMonitor {
+    /// Create a new monitor with a slope limit in celsius degrees per second
+    pub fn new(max_slope: f32) -> Self {
+        ThermalMonitor {
+            max_slope,
+            last: None,
+            slope: None,
+        }
+    }
+
+    /// Process an ambient temperature reading in celsius degrees taken at `timestamp_ms`
+    ///
+    /// Readings with the same timestamp as the previous one are ignored.
+    pub fn update(&mut self, ambient: f32, timestamp_ms: u32) -> Stability {
+        match self.last {
+            Some((last_ambient, last_timestamp_ms)) => {
+                let elapsed_ms = timestamp_ms.wrapping_sub(last_timestamp_ms);
+                if elapsed_ms != 0 {
+                    self.slope = Some((ambient - last_ambient) * 1000.0 / elapsed_ms as f32);
+                    self.last = Some((ambient, timestamp_ms));
+                }
+            }
+            None => self.last = Some((ambient, timestamp_ms)),
+        }
+        self.stability()
+    }
+
+    /// Get the last ambient temperature slope in celsius degrees per second, if known
+    pub fn slope(&self) -> Option<f32> {
+        self.slope
+    }
+
+    /// Get the current stability
+    pub fn stability(&self) -> Stability {
+        match self.slope {
+            Some(slope) if slope.abs() <= self.max_slope => Stability::Stable,
+            _ => Stability::Unstable,
+        }
+    }
+
+    /// Classify an object temperature reading according to the current stability
+    pub fn classify(&self, object: f32) -> ObjectReading {
+        match self.stability() {
+            Stability::Stable => ObjectReading::Stable(object),
+            Stability::Unstable => ObjectReading::Unstable(object),
+        }
+    }
+
+    /// Forget all readings
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.slope = None;
+    }
+}
+
+macro_rules! thermal_monitor {
+    ($ic_marker:ident, $( $(#[$attr:meta])* $method:ident: $object_method:ident ),*) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Read the ambient temperature and feed it to the monitor
+            pub fn update_thermal_monitor(
+                &mut self,
+                monitor: &mut ThermalMonitor,
+                timestamp_ms: u32,
+            ) -> Result<Stability, Error<E>> {
+                let ambient = self.ambient_temperature()?;
+                Ok(monitor.update(ambient, timestamp_ms))
+            }
+
+            /// Wait until the monitor reports the sensor as stable
+            ///
+            /// The monitor is reset first so that only readings taken while waiting
+            /// are considered. The ambient temperature is then read every `interval_ms`
+            /// milliseconds and fed to the monitor with the timestamp returned by `now_ms`.
+            /// This is a monotonic clock in milliseconds which may wrap around.
+            ///
+            /// Returns whether the sensor became stable before `timeout_ms` milliseconds elapsed.
+            pub fn wait_until_stable<D, F>(
+                &mut self,
+                monitor: &mut ThermalMonitor,
+                delay: &mut D,
+                mut now_ms: F,
+                interval_ms: u32,
+                timeout_ms: u32,
+            ) -> Result<bool, Error<E>>
+            where
+                D: DelayNs,
+                F: FnMut() -> u32,
+            {
+                monitor.reset();
+                let start_ms = now_ms();
+                let mut timestamp_ms = start_ms;
+                loop {
+                    if self.update_thermal_monitor(monitor, timestamp_ms)? == Stability::Stable {
+                        return Ok(true);
+                    }
+                    if timestamp_ms.wrapping_sub(start_ms) >= timeout_ms {
+                        return Ok(false);
+                    }
+                    delay.delay_ms(interval_ms);
+                    timestamp_ms = now_ms();
+                }
+            }
+
+            $(
+                $(#[$attr])*
+                pub fn $method(
+                    &mut self,
+                    monitor: &mut ThermalMonitor,
+                    timestamp_ms: u32,
+                ) -> Result<ObjectReading, Error<E>> {
+                    self.update_thermal_monitor(monitor, timestamp_ms)?;
+                    let object = self.$object_method()?;
+                    Ok(monitor.classify(object))
+                }
+            )*
+        }
+    };
+}
+thermal_monitor!(
+    Mlx90614,
+    /// Read the ambient temperature into the monitor and the classified object 1 temperature
+    object1_temperature_with_stability: object1_temperature,
+    /// Read the ambient temperature into the monitor and the classified object 2 temperature
+    ///
+    /// Note that this is only available in dual-zone thermopile device variants.
+    object2_temperature_with_stability: object2_temperature
+);
+thermal_monitor!(
+    Mlx90615,
+    /// Read the ambient temperature into the monitor and the classified object temperature
+    object_temperature_with_stability: object_temperature
+);
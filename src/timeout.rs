@@ -0,0 +1,151 @@
+//! SMBus timeout detection around I²C transactions
+
+use crate::Mlx9061x;
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, Operation, SevenBitAddress};
+
+/// Default SMBus timeout in microseconds
+///
+/// The devices reset their SMBus state machine if SCL is held low longer than this.
+pub const SMBUS_TIMEOUT_US: u32 = 25_000;
+
+/// Monotonic clock
+pub trait Clock {
+    /// Current time in microseconds. This may wrap around.
+    fn now_us(&mut self) -> u32;
+}
+
+/// Error of an I²C bus wrapped in `SmbusTimeout`
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutError<E> {
+    /// Error of the wrapped I²C bus
+    I2C(E),
+    /// The transaction exceeded the SMBus timeout
+    ///
+    /// Reads are only reported after a re-issued attempt also timed out.
+    /// Writes are reported after the first attempt and may have reached the device.
+    Timeout,
+}
+
+impl<E: i2c::Error> i2c::Error for TimeoutError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            TimeoutError::I2C(e) => e.kind(),
+            TimeoutError::Timeout => ErrorKind::Other,
+        }
+    }
+}
+
+/// I²C bus wrapper measuring the duration of each transaction
+///
+/// Transactions which take longer than the SMBus timeout may have been aborted
+/// by the device. Reads (`read()` and `write_read()`) are re-issued once and
+/// `TimeoutError::Timeout` is returned if the second attempt also takes too long.
+///
+/// Writes and generic transactions are not idempotent: re-sending an EEPROM
+/// erase or data word while the write cycle is running can corrupt the cell and
+/// re-sending the sleep command can put a woken device back to sleep. These
+/// are therefore never re-issued and `TimeoutError::Timeout` is returned right
+/// away.
+///
+/// Since the whole transaction is measured and not only the time SCL is held low,
+/// this is conservative.
+#[derive(Debug)]
+pub struct SmbusTimeout<I2C, C> {
+    i2c: I2C,
+    clock: C,
+    timeout_us: u32,
+    timeouts: u32,
+}
+
+impl<I2C, C> SmbusTimeout<I2C, C> {
+    /// Wrap an I²C bus with the default SMBus timeout
+    pub fn new(i2c: I2C, clock: C) -> Self {
+        SmbusTimeout {
+            i2c,
+            clock,
+            timeout_us: SMBUS_TIMEOUT_US,
+            timeouts: 0,
+        }
+    }
+
+    /// Set the timeout in microseconds
+    pub fn with_timeout_us(mut self, timeout_us: u32) -> Self {
+        self.timeout_us = timeout_us;
+        self
+    }
+
+    /// Destroy the wrapper, return the I²C bus and the clock
+    pub fn destroy(self) -> (I2C, C) {
+        (self.i2c, self.clock)
+    }
+
+    /// Number of transactions which exceeded the timeout, including re-issued ones
+    ///
+    /// This saturates at `u32::MAX`.
+    pub fn timeouts(&self) -> u32 {
+        self.timeouts
+    }
+
+    /// Reset the timeout counter
+    pub fn reset_timeouts(&mut self) {
+        self.timeouts = 0;
+    }
+}
+
+impl<I2C: I2c, C> ErrorType for SmbusTimeout<I2C, C> {
+    type Error = TimeoutError<I2C::Error>;
+}
+
+impl<I2C: I2c, C: Clock> SmbusTimeout<I2C, C> {
+    fn timed<F>(&mut self, attempts: u8, mut transfer: F) -> Result<(), TimeoutError<I2C::Error>>
+    where
+        F: FnMut(&mut I2C) -> Result<(), I2C::Error>,
+    {
+        for _ in 0..attempts {
+            let start_us = self.clock.now_us();
+            let result = transfer(&mut self.i2c);
+            if self.clock.now_us().wrapping_sub(start_us) <= self.timeout_us {
+                return result.map_err(TimeoutError::I2C);
+            }
+            self.timeouts = self.timeouts.saturating_add(1);
+        }
+        Err(TimeoutError::Timeout)
+    }
+}
+
+impl<I2C: I2c, C: Clock> I2c for SmbusTimeout<I2C, C> {
+    fn read(&mut self, address: SevenBitAddress, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.timed(2, |i2c| i2c.read(address, read))
+    }
+
+    fn write(&mut self, address: SevenBitAddress, write: &[u8]) -> Result<(), Self::Error> {
+        self.timed(1, |i2c| i2c.write(address, write))
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.timed(2, |i2c| i2c.write_read(address, write, read))
+    }
+
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.timed(1, |i2c| i2c.transaction(address, operations))
+    }
+}
+
+impl<I2C, C, IC> Mlx9061x<SmbusTimeout<I2C, C>, IC> {
+    /// Number of transactions which exceeded the SMBus timeout
+    ///
+    /// See `SmbusTimeout::timeouts()`.
+    pub fn smbus_timeouts(&self) -> u32 {
+        self.i2c.timeouts()
+    }
+}
@@ -1,3 +1,6 @@
+use core::fmt;
+use embedded_hal::i2c;
+
 /// All possible errors in this crate
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug)]
@@ -5,9 +8,141 @@ pub enum Error<E> {
     /// I²C bus error
     I2C(E),
     /// CRC checksum mismatch (PEC)
-    ChecksumMismatch,
+    ChecksumMismatch {
+        /// Register or command which was read
+        register: u8,
+        /// PEC computed from the received data
+        expected: u8,
+        /// PEC sent by the device
+        received: u8,
+    },
     /// Invalid input data
     InvalidInputData,
+    /// Invalid slave address
+    InvalidAddress(u8),
+    /// Emissivity out of the range supported by the device
+    EmissivityOutOfRange,
+    /// The device flagged the measurement as erroneous
+    MeasurementError,
+    /// The device has not completed its first measurement after power-on or wake
+    DataNotReady,
+    /// The operation is not supported by this device variant
+    NotSupported,
+    /// The value read back from the EEPROM does not match the value written
+    EepromVerificationFailed,
+    /// Write to a protected EEPROM cell with the given command
+    ProtectedCell(u8),
+    /// Another device already answers at the given address
+    AddressInUse(u8),
+}
+
+impl<E: i2c::Error> Error<E> {
+    /// Get the I²C error kind, if this is an I²C bus error
+    pub fn kind(&self) -> Option<i2c::ErrorKind> {
+        match self {
+            Error::I2C(e) => Some(e.kind()),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::I2C(e) => write!(f, "I²C bus error: {:?}", e),
+            Error::ChecksumMismatch {
+                register,
+                expected,
+                received,
+            } => write!(
+                f,
+                "PEC mismatch reading register {:#04x}: expected {:#04x}, received {:#04x}",
+                register, expected, received
+            ),
+            Error::InvalidInputData => write!(f, "invalid input data"),
+            Error::InvalidAddress(address) => write!(f, "invalid slave address {:#04x}", address),
+            Error::EmissivityOutOfRange => write!(f, "emissivity out of range"),
+            Error::MeasurementError => write!(f, "measurement error flag set"),
+            Error::DataNotReady => write!(f, "valid data not yet available"),
+            Error::NotSupported => write!(f, "not supported by this device variant"),
+            Error::EepromVerificationFailed => write!(f, "EEPROM verification failed"),
+            Error::ProtectedCell(command) => {
+                write!(f, "write to protected EEPROM cell {:#04x}", command)
+            }
+            Error::AddressInUse(address) => {
+                write!(f, "another device answers at address {:#04x}", address)
+            }
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::I2C(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Raw MLX90614 EEPROM cells involved in an emissivity change
+///
+/// This is returned by `set_emissivity()` with the previous contents so that
+/// the change can be undone with `restore_emissivity()`.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmissivityCalibration {
+    /// Emissivity cell (0x04) raw value
+    pub emissivity: u16,
+    /// Factory-calibrated cell 0x0F raw value
+    pub calibration: u16,
+}
+
+/// Object temperatures of both zones of a dual-zone MLX90614
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DualZoneReading {
+    /// Object 1 temperature in celsius degrees
+    pub object1: f32,
+    /// Object 2 temperature in celsius degrees
+    pub object2: f32,
+}
+
+impl DualZoneReading {
+    /// Object 1 temperature minus object 2 temperature
+    pub fn difference(&self) -> f32 {
+        self.object1 - self.object2
+    }
+
+    /// Average of both object temperatures
+    pub fn average(&self) -> f32 {
+        (self.object1 + self.object2) / 2.0
+    }
+}
+
+/// Address change stored in the EEPROM but not yet in use by the device
+///
+/// The device keeps answering at the old address until it is power cycled.
+/// This is returned by `set_address()`. After the power cycle, pass it to
+/// `confirm_address_change()` so that the driver uses the new address.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Eq)]
+#[must_use = "the driver keeps using the old address until the change is confirmed"]
+pub struct PendingAddressChange {
+    pub(crate) old: u8,
+    pub(crate) new: u8,
+}
+
+impl PendingAddressChange {
+    /// Address the device answers at until it is power cycled
+    pub fn old_address(&self) -> u8 {
+        self.old
+    }
+
+    /// Address the device will answer at after a power cycle
+    pub fn new_address(&self) -> u8 {
+        self.new
+    }
 }
 
 /// IC marker
@@ -26,6 +161,12 @@ pub enum SlaveAddr {
     Default,
     /// Alternative slave address
     Alternative(u8),
+    /// SMBus broadcast address 0x00
+    ///
+    /// Every device answers at this address regardless of its programmed address.
+    /// Only use this when a single device is connected to the bus, for example,
+    /// to recover a device whose address is unknown.
+    Broadcast,
 }
 
 impl Default for SlaveAddr {
@@ -0,0 +1,142 @@
+//! MLX90614 part variant identification
+
+use crate::{ic, register_access::mlx90614::Register, AccuracyVariant, Error, Grade, Mlx9061x};
+use embedded_hal::i2c::I2c;
+
+/// Number of thermopile zones
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zones {
+    /// Single-zone thermopile
+    Single,
+    /// Dual-zone thermopile
+    Dual,
+}
+
+/// Supply voltage
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Supply {
+    /// 5V (MLX90614Axx)
+    V5,
+    /// 3V (MLX90614Bxx and MLX90614Dxx)
+    V3,
+}
+
+/// MLX90614 part variant
+///
+/// This can be read from the device with `variant()`, where only the properties
+/// stored in the EEPROM can be derived, or supplied manually with `set_variant()`.
+/// e.g. from the part code with `from_code()`.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Variant {
+    /// Number of thermopile zones
+    pub zones: Zones,
+    /// Amplifier gain
+    pub gain: Option<f32>,
+    /// Supply voltage
+    pub supply: Option<Supply>,
+    /// Accuracy grade
+    pub grade: Grade,
+    /// Whether the thermal gradient is compensated
+    pub gradient_compensated: Option<bool>,
+    /// Field of view in degrees
+    pub field_of_view: Option<u8>,
+}
+
+impl Variant {
+    /// Decode a part code like `"DCI"` (from MLX90614ESF-DCI)
+    ///
+    /// The first letter encodes the supply voltage and grade, the second one the number
+    /// of zones and gradient compensation and the third one the field of view.
+    /// Returns `None` for unknown supply or zone codes. Unknown field of view codes
+    /// are left as `None`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let mut chars = code.chars();
+        let (supply, grade) = match chars.next()? {
+            'A' => (Supply::V5, Grade::Standard),
+            'B' => (Supply::V3, Grade::Standard),
+            'D' => (Supply::V3, Grade::Medical),
+            _ => return None,
+        };
+        let (zones, gradient_compensated) = match chars.next()? {
+            'A' => (Zones::Single, false),
+            'B' => (Zones::Dual, false),
+            'C' => (Zones::Single, true),
+            _ => return None,
+        };
+        let field_of_view = match chars.next() {
+            Some('A') => Some(90),
+            Some('C') => Some(35),
+            Some('F') => Some(10),
+            Some('H') => Some(12),
+            Some('I') => Some(5),
+            _ => None,
+        };
+        Some(Variant {
+            zones,
+            gain: None,
+            supply: Some(supply),
+            grade,
+            gradient_compensated: Some(gradient_compensated),
+            field_of_view,
+        })
+    }
+
+    /// Decode the properties stored in ConfigRegister1
+    pub(crate) fn from_config(config: u16) -> Self {
+        let zones = if config & (1 << 6) != 0 {
+            Zones::Dual
+        } else {
+            Zones::Single
+        };
+        let gain = match (config >> 11) & 0b111 {
+            0 => 1.0,
+            1 => 3.0,
+            2 => 6.0,
+            3 => 12.5,
+            4 => 25.0,
+            5 => 50.0,
+            _ => 100.0,
+        };
+        Variant {
+            zones,
+            gain: Some(gain),
+            supply: None,
+            grade: Grade::Standard,
+            gradient_compensated: None,
+            field_of_view: None,
+        }
+    }
+
+    /// Variant for the accuracy lookup. See `accuracy()`.
+    pub fn accuracy_variant(&self) -> AccuracyVariant {
+        AccuracyVariant::Mlx90614(self.grade)
+    }
+}
+
+impl<E, I2C> Mlx9061x<I2C, ic::Mlx90614>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Get the part variant
+    ///
+    /// Unless supplied with `set_variant()`, this is read from the EEPROM once and
+    /// cached. Only the number of zones and the gain can be derived. The rest of the
+    /// properties are left unknown and the grade is assumed to be standard.
+    pub fn variant(&mut self) -> Result<Variant, Error<E>> {
+        if let Some(variant) = self.variant {
+            return Ok(variant);
+        }
+        let config = self.read_u16(Register::CONFIG1)?;
+        let variant = Variant::from_config(config);
+        self.variant = Some(variant);
+        Ok(variant)
+    }
+
+    /// Supply the part variant manually
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = Some(variant);
+    }
+}
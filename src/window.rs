@@ -0,0 +1,116 @@
+//! Optical window transmission compensation
+
+use crate::{
+    ic,
+    radiometry::{celsius_from_pow4, kelvin_pow4},
+    Error, Mlx9061x,
+};
+use embedded_hal::i2c::I2c;
+
+/// Optical window placed in front of the sensor
+///
+/// Protection windows (e.g. germanium or polyethylene) attenuate the IR radiation
+/// coming from the target so that the sensor reads too low. The window also
+/// emits radiation according to its own temperature.
+///
+/// The window is assumed to be non-reflective, so that its emissivity is
+/// `1 - transmissivity`. Unless otherwise configured, the window is assumed
+/// to be at the ambient temperature of the sensor.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpticalWindow {
+    transmissivity: f32,
+    temperature: Option<f32>,
+}
+
+impl OpticalWindow {
+    /// Create a window with the given transmissivity ]0.0-1.0]
+    ///
+    /// Returns `None` if the transmissivity is out of range.
+    pub fn new(transmissivity: f32) -> Option<Self> {
+        if transmissivity <= 0.0 || transmissivity > 1.0 {
+            return None;
+        }
+        Some(OpticalWindow {
+            transmissivity,
+            temperature: None,
+        })
+    }
+
+    /// Set the window temperature in celsius degrees
+    ///
+    /// Otherwise the window is assumed to be at the ambient temperature.
+    pub fn with_temperature(self, temperature: f32) -> Self {
+        OpticalWindow {
+            temperature: Some(temperature),
+            ..self
+        }
+    }
+
+    /// Get the window transmissivity
+    pub fn transmissivity(&self) -> f32 {
+        self.transmissivity
+    }
+
+    /// Get the window temperature in celsius degrees, if configured
+    pub fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    /// Compute the target temperature from a temperature measured through the window
+    ///
+    /// All temperatures are in celsius degrees. `emissivity` is the emissivity
+    /// configured in the device when the measurement was taken.
+    pub fn target_temperature(&self, measured: f32, ambient: f32, emissivity: f32) -> f32 {
+        let ta4 = kelvin_pow4(ambient);
+        let tw4 = self.temperature.map_or(ta4, kelvin_pow4);
+        let tr4 = kelvin_pow4(measured);
+        let window = (1.0 - self.transmissivity) * (tw4 - ta4) / emissivity;
+        let to4 = ta4 + (tr4 - ta4 - window) / self.transmissivity;
+        celsius_from_pow4(to4)
+    }
+
+    /// Emissivity to program with `set_emissivity()` so that the device compensates the window
+    ///
+    /// This is only accurate while the window is at the ambient temperature of the sensor.
+    /// The resulting value must still be within the range supported by the device.
+    pub fn equivalent_emissivity(&self, target_emissivity: f32) -> f32 {
+        self.transmissivity * target_emissivity
+    }
+}
+
+macro_rules! window_compensation {
+    ($ic_marker:ident, $method:ident, $object_method:ident) => {
+        impl<E, I2C> Mlx9061x<I2C, ic::$ic_marker>
+        where
+            I2C: I2c<Error = E>,
+        {
+            /// Read the object temperature in celsius degrees compensating an optical window
+            ///
+            /// The ambient temperature will be read as well and if the window temperature
+            /// is configured, the emissivity will be read as well.
+            pub fn $method(&mut self, window: &OpticalWindow) -> Result<f32, Error<E>> {
+                let measured = self.$object_method()?;
+                let ambient = self.ambient_temperature()?;
+                let emissivity = match window.temperature() {
+                    Some(_) => self.emissivity()?,
+                    None => 1.0,
+                };
+                if emissivity <= 0.0 {
+                    return Err(Error::InvalidInputData);
+                }
+                Ok(window.target_temperature(measured, ambient, emissivity))
+            }
+        }
+    };
+}
+window_compensation!(
+    Mlx90614,
+    object1_temperature_through_window,
+    object1_temperature
+);
+window_compensation!(
+    Mlx90615,
+    object_temperature_through_window,
+    object_temperature
+);
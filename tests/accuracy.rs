@@ -0,0 +1,87 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{accuracy, AccuracyVariant, Grade, Variant};
+
+#[test]
+fn standard_accuracy_zones() {
+    let variant = AccuracyVariant::Mlx90614(Grade::Standard);
+    assert_eq!(Some(0.5), accuracy(25.0, 37.0, variant));
+    assert_eq!(Some(1.0), accuracy(25.0, 100.0, variant));
+    assert_eq!(Some(2.0), accuracy(70.0, 100.0, variant));
+    assert_eq!(Some(4.0), accuracy(25.0, 300.0, variant));
+    assert_eq!(None, accuracy(25.0, 400.0, variant));
+}
+
+#[test]
+fn medical_accuracy_zones() {
+    let variant = AccuracyVariant::Mlx90615(Grade::Medical);
+    assert_eq!(Some(0.2), accuracy(25.0, 37.0, variant));
+    assert_eq!(Some(0.5), accuracy(25.0, 20.0, variant));
+    assert_eq!(
+        Some(0.5),
+        accuracy(25.0, 37.0, AccuracyVariant::Mlx90615(Grade::Standard))
+    );
+}
+
+#[test]
+fn can_measure_with_uncertainty() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::CONFIG1],
+            vec![0xB4, 0x9F, 43],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![193, 66, 55],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![59, 58, 216],
+        ),
+    ]);
+    let measurement = sensor.measure_with_uncertainty().unwrap();
+    assert_near!(measurement.object, 68.63, 0.01);
+    assert_near!(measurement.ambient, 24.99, 0.01);
+    assert_eq!(Some(1.0), measurement.tolerance);
+    destroy(sensor);
+}
+
+fn medical_reads() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![0x94, 0x3C, 7],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![59, 58, 216],
+        ),
+    ]
+}
+
+#[test]
+fn uncertainty_uses_variant_grade() {
+    let mut sensor = new_mlx90614(&medical_reads());
+    sensor.set_variant(Variant::from_code("DCI").unwrap());
+    let measurement = sensor.measure_with_uncertainty().unwrap();
+    assert_near!(measurement.object, 37.01, 0.01);
+    assert_eq!(Some(0.2), measurement.tolerance);
+    destroy(sensor);
+}
+
+#[test]
+fn can_override_uncertainty_grade() {
+    let mut sensor = new_mlx90614(&medical_reads());
+    sensor.set_variant(Variant::from_code("DCI").unwrap());
+    let measurement = sensor
+        .measure_with_uncertainty_for_grade(Grade::Standard)
+        .unwrap();
+    assert_eq!(Some(0.5), measurement.tolerance);
+    destroy(sensor);
+}
@@ -0,0 +1,106 @@
+use mlx9061x::{Alarm, AlarmConfig, AlarmEvent, AlarmLevel, AlarmState, Error};
+
+fn new_alarm(debounce: u8) -> Alarm {
+    Alarm::new(AlarmConfig {
+        high: 30.0,
+        low: 10.0,
+        hysteresis: 1.0,
+        debounce,
+    })
+    .unwrap()
+}
+
+fn ok(t: f32) -> Result<f32, Error<()>> {
+    Ok(t)
+}
+
+#[test]
+fn invalid_config_is_rejected() {
+    let config = AlarmConfig {
+        high: 10.0,
+        low: 10.0,
+        hysteresis: 1.0,
+        debounce: 1,
+    };
+    assert!(Alarm::new(config).is_none());
+    let config = AlarmConfig {
+        high: 30.0,
+        low: 10.0,
+        hysteresis: -1.0,
+        debounce: 1,
+    };
+    assert!(Alarm::new(config).is_none());
+}
+
+#[test]
+fn raises_and_clears_with_hysteresis() {
+    let mut alarm = new_alarm(1);
+    assert_eq!(None, alarm.update(ok(25.0)));
+    assert_eq!(
+        Some(AlarmEvent::Raised(AlarmLevel::High)),
+        alarm.update(ok(30.5))
+    );
+    assert_eq!(None, alarm.update(ok(29.5)));
+    assert_eq!(AlarmState::Raised(AlarmLevel::High), alarm.state());
+    assert_eq!(
+        Some(AlarmEvent::Cleared(AlarmLevel::High)),
+        alarm.update(ok(28.5))
+    );
+    assert_eq!(AlarmState::Normal, alarm.state());
+}
+
+#[test]
+fn raises_low_alarm() {
+    let mut alarm = new_alarm(1);
+    assert_eq!(
+        Some(AlarmEvent::Raised(AlarmLevel::Low)),
+        alarm.update(ok(9.0))
+    );
+    assert_eq!(None, alarm.update(ok(10.5)));
+    assert_eq!(
+        Some(AlarmEvent::Cleared(AlarmLevel::Low)),
+        alarm.update(ok(11.5))
+    );
+}
+
+#[test]
+fn debounces_state_changes() {
+    let mut alarm = new_alarm(3);
+    assert_eq!(None, alarm.update(ok(31.0)));
+    assert_eq!(None, alarm.update(ok(31.0)));
+    assert_eq!(None, alarm.update(ok(25.0)));
+    assert_eq!(None, alarm.update(ok(31.0)));
+    assert_eq!(None, alarm.update(ok(31.0)));
+    assert_eq!(
+        Some(AlarmEvent::Raised(AlarmLevel::High)),
+        alarm.update(ok(31.0))
+    );
+}
+
+#[test]
+fn failed_readings_lead_to_sensor_fault() {
+    let mut alarm = new_alarm(2);
+    assert_eq!(
+        None,
+        alarm.update::<()>(Err(Error::ChecksumMismatch {
+            register: 0,
+            expected: 0,
+            received: 1
+        }))
+    );
+    assert_eq!(
+        Some(AlarmEvent::SensorFault),
+        alarm.update::<()>(Err(Error::MeasurementError))
+    );
+    assert_eq!(AlarmState::SensorFault, alarm.state());
+    assert_eq!(None, alarm.update(ok(25.0)));
+    assert_eq!(Some(AlarmEvent::SensorRecovered), alarm.update(ok(25.0)));
+}
+
+#[test]
+fn can_reset() {
+    let mut alarm = new_alarm(1);
+    alarm.update(ok(31.0));
+    alarm.reset();
+    assert_eq!(AlarmState::Normal, alarm.state());
+}
@@ -14,7 +14,9 @@ pub mod mlx90614 {
         pub const TOBJ1: u8 = 0x07;
         pub const TOBJ2: u8 = 0x08;
         pub const EMISSIVITY: u8 = 0x04 | EEPROM_COMMAND;
+        pub const CONFIG1: u8 = 0x05 | EEPROM_COMMAND;
         pub const ADDRESS: u8 = 0x0E | EEPROM_COMMAND;
+        pub const EMISSIVITY_CALIBRATION: u8 = 0x0F | EEPROM_COMMAND;
         pub const ID0: u8 = 0x1C | EEPROM_COMMAND;
     }
 }
@@ -31,6 +33,7 @@ pub mod mlx90615 {
         pub const TA: u8 = 0x06 | RAM_COMMAND;
         pub const TOBJ: u8 = 0x07 | RAM_COMMAND;
         pub const ADDRESS: u8 = /*0x00 |*/ EEPROM_COMMAND;
+        pub const CONFIG: u8 = 0x02 | EEPROM_COMMAND;
         pub const EMISSIVITY: u8 = 0x03 | EEPROM_COMMAND;
         pub const ID0: u8 = 0x0E | EEPROM_COMMAND;
     }
@@ -41,11 +44,24 @@ pub fn new_mlx90614(transactions: &[I2cTrans]) -> Mlx9061x<I2cMock, ic::Mlx90614
     Mlx9061x::new_mlx90614(I2cMock::new(transactions), SlaveAddr::default(), 5).unwrap()
 }
 
+/// MLX90614 which reports a dual-zone thermopile in ConfigRegister1
+#[allow(unused)]
+pub fn new_mlx90614_dual_zone(transactions: &[I2cTrans]) -> Mlx9061x<I2cMock, ic::Mlx90614> {
+    let mut all = vec![I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::CONFIG1],
+        vec![0xF4, 0x9F, 112],
+    )];
+    all.extend_from_slice(transactions);
+    new_mlx90614(&all)
+}
+
 #[allow(unused)]
 pub fn new_mlx90615(transactions: &[I2cTrans]) -> Mlx9061x<I2cMock, ic::Mlx90615> {
     Mlx9061x::new_mlx90615(I2cMock::new(transactions), SlaveAddr::default(), 5).unwrap()
 }
 
+#[allow(unused)]
 pub fn destroy<IC>(sensor: Mlx9061x<I2cMock, IC>) {
     sensor.destroy().done();
 }
@@ -69,7 +85,7 @@ macro_rules! assert_crc_mismatch {
 macro_rules! assert_error {
     ($result: expr, $error:ident) => {
         match $result {
-            Err(Error::$error) => (),
+            Err(Error::$error { .. }) => (),
             _ => panic!("Should have returned error."),
         }
     };
@@ -0,0 +1,67 @@
+mod base;
+use crate::base::{mlx90614, mlx90615};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+};
+use mlx9061x::{Error, Mlx9061x, SlaveAddr};
+
+const BROADCAST_ADDR: u8 = 0x00;
+
+#[test]
+fn can_read_mlx90614_via_broadcast() {
+    let mut i2c = I2cMock::new(&[I2cTrans::write_read(
+        BROADCAST_ADDR,
+        vec![mlx90614::Register::TOBJ1],
+        vec![38, 58, 127],
+    )]);
+    let mut sensor = Mlx9061x::new_mlx90614(i2c.clone(), SlaveAddr::Broadcast, 5).unwrap();
+    let t = sensor.object1_temperature().unwrap();
+    assert_near!(t, 24.57, 0.01);
+    i2c.done();
+}
+
+#[test]
+fn can_recover_mlx90614_address_via_broadcast() {
+    use mlx90614::Register as Reg;
+    let mut i2c = I2cMock::new(&[
+        I2cTrans::write_read(0x5C, vec![mlx90614::Register::TA], vec![0, 0, 0])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        I2cTrans::write(BROADCAST_ADDR, vec![Reg::ADDRESS, 0, 0, 111]),
+        I2cTrans::write(BROADCAST_ADDR, vec![Reg::ADDRESS, 0x5C, 0, 159]),
+    ]);
+    let mut sensor = Mlx9061x::new_mlx90614(i2c.clone(), SlaveAddr::Broadcast, 5).unwrap();
+    let change = sensor
+        .set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {})
+        .unwrap();
+    assert_eq!(BROADCAST_ADDR, change.old_address());
+    assert_eq!(0x5C, change.new_address());
+    i2c.done();
+}
+
+#[test]
+fn can_access_mlx90615_via_broadcast() {
+    use mlx90615::Register as Reg;
+    let mut i2c = I2cMock::new(&[
+        I2cTrans::write_read(BROADCAST_ADDR, vec![Reg::TA], vec![225, 57, 40]),
+        I2cTrans::write(BROADCAST_ADDR, vec![Reg::EMISSIVITY, 0, 0, 31]),
+        I2cTrans::write(BROADCAST_ADDR, vec![Reg::EMISSIVITY, 0, 0x40, 216]),
+    ]);
+    let mut sensor = Mlx9061x::new_mlx90615(i2c.clone(), SlaveAddr::Broadcast, 5).unwrap();
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.01);
+    sensor.set_emissivity(1.0, &mut NoopDelay {}).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn cannot_set_broadcast_address() {
+    let mut i2c = I2cMock::new(&[]);
+    let mut sensor = Mlx9061x::new_mlx90615(i2c.clone(), SlaveAddr::default(), 5).unwrap();
+    assert_error!(
+        sensor.set_address(SlaveAddr::Broadcast, &mut NoopDelay {}),
+        InvalidAddress
+    );
+    i2c.done();
+}
@@ -0,0 +1,89 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::{Calibrated, Calibration, CalibrationPoint, Error};
+
+#[test]
+fn default_calibration_is_identity() {
+    let calibration = Calibration::default();
+    assert_near!(calibration.apply(24.57), 24.57, 0.0001);
+}
+
+#[test]
+fn can_fit_two_points() {
+    let calibration = Calibration::from_two_points(
+        CalibrationPoint {
+            measured: 20.2,
+            reference: 20.0,
+        },
+        CalibrationPoint {
+            measured: 40.6,
+            reference: 40.0,
+        },
+    )
+    .unwrap();
+    assert_near!(calibration.apply(20.2), 20.0, 0.001);
+    assert_near!(calibration.apply(40.6), 40.0, 0.001);
+    assert_near!(calibration.apply(30.4), 30.0, 0.001);
+}
+
+#[test]
+fn two_points_at_same_temperature_cannot_be_fitted() {
+    let point = CalibrationPoint {
+        measured: 20.2,
+        reference: 20.0,
+    };
+    assert!(Calibration::from_two_points(point, point).is_none());
+}
+
+#[test]
+fn can_serialize_and_deserialize() {
+    let calibration = Calibration::new(0.98, -0.25);
+    let bytes = calibration.to_bytes();
+    assert_eq!(calibration, Calibration::from_bytes(bytes));
+}
+
+#[test]
+fn can_measure_calibration_point() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![38, 58, 112],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![225, 57, 255],
+        ),
+    ]);
+    let point = sensor
+        .measure_calibration_point(23.5, 2, 10, &mut NoopDelay {})
+        .unwrap();
+    assert_near!(point.measured, 23.88, 0.01);
+    assert_near!(point.reference, 23.5, 0.0001);
+    destroy(sensor);
+}
+
+#[test]
+fn measure_calibration_point_without_samples_returns_error() {
+    let mut sensor = new_mlx90615(&[]);
+    assert_error!(
+        sensor.measure_calibration_point(23.5, 0, 10, &mut NoopDelay {}),
+        InvalidInputData
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn calibrated_wrapper_applies_calibration() {
+    let sensor = new_mlx90615(&[I2cTrans::write_read(
+        mlx90615::DEV_ADDR,
+        vec![mlx90615::Register::TOBJ],
+        vec![38, 58, 172],
+    )]);
+    let mut sensor = Calibrated::new(sensor, Calibration::new(1.0, -0.5));
+    let t = sensor.object_temperature().unwrap();
+    assert_near!(t, 24.07, 0.01);
+    destroy(sensor.destroy());
+}
@@ -1,10 +1,12 @@
 mod base;
 use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
 use embedded_hal_mock::eh1::{
     delay::NoopDelay,
     i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    pin::{Mock as PinMock, State as PinState, Transaction as PinTrans},
 };
-use mlx9061x::{Error, Mlx9061x, SlaveAddr};
+use mlx9061x::{recover_bus, Error, Mlx9061x, SlaveAddr};
 
 macro_rules! tests {
     ($create:ident, $ic:ident) => {
@@ -19,7 +21,7 @@ macro_rules! tests {
             let mut below_min_mock = I2cMock::new(&[]);
             assert_error!(
                 Mlx9061x::$create(below_min_mock.clone(), SlaveAddr::Alternative(0), 5),
-                InvalidInputData
+                InvalidAddress
             );
             below_min_mock.done();
         }
@@ -29,7 +31,7 @@ macro_rules! tests {
             let mut above_max_mock = I2cMock::new(&[]);
             assert_error!(
                 Mlx9061x::$create(above_max_mock.clone(), SlaveAddr::Alternative(128), 5),
-                InvalidInputData
+                InvalidAddress
             );
             above_max_mock.done();
         }
@@ -48,19 +50,78 @@ macro_rules! tests {
         #[test]
         fn set_wrong_address_returns_error() {
             let mut sensor = $create(&[]);
+            for address in [0x00, 0x07, 0x78, 0x7F, 0x80] {
+                assert_error!(
+                    sensor.set_address(SlaveAddr::Alternative(address), &mut NoopDelay {}),
+                    InvalidAddress
+                );
+            }
+            destroy(sensor);
+        }
+
+        #[test]
+        fn set_address_in_use_returns_error() {
+            let mut sensor = $create(&[I2cTrans::write_read(
+                0x5C,
+                vec![$ic::Register::TA],
+                vec![0, 0, 0],
+            )]);
+            assert_error!(
+                sensor.set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {}),
+                AddressInUse
+            );
+            destroy(sensor);
+        }
+
+        #[test]
+        fn set_address_in_use_with_data_nack_returns_error() {
+            let mut sensor =
+                $create(&[
+                    I2cTrans::write_read(0x5C, vec![$ic::Register::TA], vec![0, 0, 0])
+                        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)),
+                ]);
             assert_error!(
-                sensor.set_address(SlaveAddr::Alternative(0), &mut NoopDelay {}),
-                InvalidInputData
+                sensor.set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {}),
+                AddressInUse
             );
             destroy(sensor);
         }
 
+        #[test]
+        fn set_address_probe_unknown_nack_is_returned() {
+            let mut sensor =
+                $create(&[
+                    I2cTrans::write_read(0x5C, vec![$ic::Register::TA], vec![0, 0, 0])
+                        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)),
+                ]);
+            assert_error!(
+                sensor.set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {}),
+                I2C
+            );
+            destroy(sensor);
+        }
+
+        #[test]
+        fn set_address_probe_bus_error_is_returned() {
+            let mut sensor =
+                $create(&[
+                    I2cTrans::write_read(0x5C, vec![$ic::Register::TA], vec![0, 0, 0])
+                        .with_error(ErrorKind::ArbitrationLoss),
+                ]);
+            assert_error!(
+                sensor.set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {}),
+                I2C
+            );
+            assert_eq!(1, sensor.bus_stats().i2c_errors);
+            destroy(sensor);
+        }
+
         #[test]
         fn set_wrong_too_small_emissivity_returns_error() {
             let mut sensor = $create(&[]);
             assert_error!(
                 sensor.set_emissivity(-0.1, &mut NoopDelay {}),
-                InvalidInputData
+                EmissivityOutOfRange
             );
             destroy(sensor);
         }
@@ -70,7 +131,7 @@ macro_rules! tests {
             let mut sensor = $create(&[]);
             assert_error!(
                 sensor.set_emissivity(1.1, &mut NoopDelay {}),
-                InvalidInputData
+                EmissivityOutOfRange
             );
             destroy(sensor);
         }
@@ -86,3 +147,54 @@ mod mlx90615_tests {
     use super::*;
     tests!(new_mlx90615, mlx90615);
 }
+
+fn stop_condition() -> (Vec<PinTrans>, Vec<PinTrans>) {
+    (
+        vec![PinTrans::set(PinState::Low), PinTrans::set(PinState::High)],
+        vec![
+            PinTrans::set(PinState::Low),
+            PinTrans::set(PinState::High),
+            PinTrans::get(PinState::High),
+        ],
+    )
+}
+
+#[test]
+fn can_recover_bus() {
+    let (scl_stop, sda_stop) = stop_condition();
+    let mut scl_trans = vec![PinTrans::set(PinState::High)];
+    let mut sda_trans = vec![PinTrans::set(PinState::High)];
+    for _ in 0..2 {
+        scl_trans.push(PinTrans::set(PinState::Low));
+        scl_trans.push(PinTrans::set(PinState::High));
+        sda_trans.push(PinTrans::get(PinState::Low));
+    }
+    sda_trans.push(PinTrans::get(PinState::High));
+    scl_trans.extend(scl_stop);
+    sda_trans.extend(sda_stop);
+    let mut scl = PinMock::new(&scl_trans);
+    let mut sda = PinMock::new(&sda_trans);
+    assert!(recover_bus(&mut scl, &mut sda, &mut NoopDelay::new()).unwrap());
+    scl.done();
+    sda.done();
+}
+
+#[test]
+fn recover_bus_reports_stuck_bus() {
+    let (scl_stop, mut sda_stop) = stop_condition();
+    let mut scl_trans = vec![PinTrans::set(PinState::High)];
+    let mut sda_trans = vec![PinTrans::set(PinState::High)];
+    for _ in 0..9 {
+        scl_trans.push(PinTrans::set(PinState::Low));
+        scl_trans.push(PinTrans::set(PinState::High));
+        sda_trans.push(PinTrans::get(PinState::Low));
+    }
+    scl_trans.extend(scl_stop);
+    sda_stop[2] = PinTrans::get(PinState::Low);
+    sda_trans.extend(sda_stop);
+    let mut scl = PinMock::new(&scl_trans);
+    let mut sda = PinMock::new(&sda_trans);
+    assert!(!recover_bus(&mut scl, &mut sda, &mut NoopDelay::new()).unwrap());
+    scl.done();
+    sda.done();
+}
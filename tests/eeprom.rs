@@ -0,0 +1,319 @@
+mod base;
+use crate::base::{destroy, mlx90615, new_mlx90615};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{diff, CellClass, CellValue, EepromImage, EepromLayout};
+
+const MLX90615_CELLS: [u16; 16] = [
+    0x355B, 0x09C0, 0x1439, 0x4000, 0x2B00, 0x0480, 0x3F00, 0x0B0E, 0x1F9A, 0x8B4A, 0x0C3A, 0x5A60,
+    0x0000, 0x0000, 0x0123, 0x4567,
+];
+
+fn mlx90614_cells() -> [u16; 32] {
+    let mut cells = [0; 32];
+    cells[0x00] = 0x9993;
+    cells[0x02] = 0x0201;
+    cells[0x03] = 0xF71C;
+    cells[0x04] = 0xFFFF;
+    cells[0x05] = 0x9FB4;
+    cells[0x0E] = 0xBE5A;
+    cells[0x1C..].copy_from_slice(&[0x0123, 0x4567, 0x89AB, 0xCDEF]);
+    cells
+}
+
+#[test]
+fn can_read_mlx90615_image() {
+    let address = mlx90615::DEV_ADDR;
+    let transactions: Vec<I2cTrans> = MLX90615_CELLS
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let register = mlx90615::Register::ADDRESS + i as u8;
+            let [lsb, msb] = cell.to_le_bytes();
+            let pec = smbus_pec::pec(&[address << 1, register, (address << 1) + 1, lsb, msb]);
+            I2cTrans::write_read(address, vec![register], vec![lsb, msb, pec])
+        })
+        .collect();
+    let mut sensor = new_mlx90615(&transactions);
+    let image = sensor.read_eeprom_image().unwrap();
+    assert_eq!(EepromLayout::Mlx90615, image.layout());
+    assert_eq!(&MLX90615_CELLS, image.cells());
+    assert_eq!(0x0123_4567, image.device_id());
+    destroy(sensor);
+}
+
+#[test]
+fn image_requires_matching_cell_count() {
+    assert_eq!(
+        None,
+        EepromImage::new(EepromLayout::Mlx90614, &MLX90615_CELLS)
+    );
+    assert_eq!(None, EepromImage::new(EepromLayout::Mlx90615, &[0; 32]));
+}
+
+#[test]
+fn can_decode_mlx90614_cells() {
+    let image = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+    assert_eq!(0x0123_4567_89AB_CDEF, image.device_id());
+    assert_eq!(Some(0x9FB4), image.cell(0x05));
+    assert_eq!(None, image.cell(0x20));
+    assert_eq!(Some(CellValue::Emissivity(1.0)), image.decode(0x04));
+    assert_eq!(Some(CellValue::Address(0x5A)), image.decode(0x0E));
+    assert_eq!(Some(CellValue::Id(0x89AB)), image.decode(0x1E));
+    assert_eq!(Some(CellValue::Factory(0)), image.decode(0x08));
+    assert_eq!(
+        Some(CellValue::TaRange {
+            min: 0x1C,
+            max: 0xF7
+        }),
+        image.decode(0x03)
+    );
+    assert_eq!(
+        Some(CellValue::PwmControl {
+            single_mode: true,
+            pwm_enabled: false,
+            sda_push_pull: false,
+            thermal_relay: false,
+            repetition: 0,
+            period: 1,
+        }),
+        image.decode(0x02)
+    );
+    match image.decode(0x05) {
+        Some(CellValue::Config1 {
+            iir,
+            fir,
+            dual_zone,
+            ..
+        }) => {
+            assert_eq!(4, iir);
+            assert_eq!(1024, fir);
+            assert!(!dual_zone);
+        }
+        value => panic!("unexpected value {:?}", value),
+    }
+    match image.decode(0x00) {
+        Some(CellValue::PwmTemperature(t)) => {
+            assert_near!(t, 120.0, 0.01);
+        }
+        value => panic!("unexpected value {:?}", value),
+    }
+}
+
+#[test]
+fn can_decode_mlx90615_cells() {
+    let image = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+    assert_eq!(Some(CellValue::Address(0x5B)), image.decode(0x00));
+    assert_eq!(Some(CellValue::PwmRange(0x09C0)), image.decode(0x01));
+    assert_eq!(Some(CellValue::Config { iir: 1 }), image.decode(0x02));
+    assert_eq!(Some(CellValue::Emissivity(1.0)), image.decode(0x03));
+    assert_eq!(Some(CellValue::Factory(0x2B00)), image.decode(0x04));
+    assert_eq!(None, image.decode(0x10));
+}
+
+#[test]
+fn can_query_layout() {
+    assert_eq!(Some("Config1"), EepromLayout::Mlx90614.cell_name(0x05));
+    assert_eq!(Some("Factory"), EepromLayout::Mlx90614.cell_name(0x10));
+    assert_eq!(None, EepromLayout::Mlx90615.cell_name(0x10));
+    assert!(EepromLayout::Mlx90614.is_customer_writable(0x0E));
+    assert!(!EepromLayout::Mlx90614.is_customer_writable(0x0F));
+    assert!(EepromLayout::Mlx90615.is_customer_writable(0x03));
+    assert!(!EepromLayout::Mlx90615.is_customer_writable(0x0E));
+}
+
+#[test]
+fn diff_of_equal_images_is_empty() {
+    let image = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+    assert_eq!(0, diff(&image, &image).unwrap().count());
+}
+
+#[test]
+fn diff_requires_same_layout() {
+    let a = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+    let b = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+    assert!(diff(&a, &b).is_none());
+}
+
+#[test]
+fn diff_explains_changed_cells() {
+    let a = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+    let mut cells = mlx90614_cells();
+    cells[0x04] = 0xF332;
+    cells[0x0E] = 0xBE5C;
+    cells[0x0F] = 0x1234;
+    let b = EepromImage::new(EepromLayout::Mlx90614, &cells).unwrap();
+    let diffs: Vec<_> = diff(&a, &b).unwrap().collect();
+    assert_eq!(3, diffs.len());
+
+    assert_eq!(0x04, diffs[0].address);
+    assert_eq!("Emissivity", diffs[0].name);
+    assert_eq!(CellClass::CustomerWritable, diffs[0].class);
+    assert_eq!(CellValue::Emissivity(1.0), diffs[0].a);
+    match diffs[0].b {
+        CellValue::Emissivity(e) => {
+            assert_near!(e, 0.95, 0.001);
+        }
+        value => panic!("unexpected value {:?}", value),
+    }
+
+    assert_eq!(CellValue::Address(0x5A), diffs[1].a);
+    assert_eq!(CellValue::Address(0x5C), diffs[1].b);
+    assert_eq!(0x0006, diffs[1].changed_bits());
+
+    assert_eq!("EmissivityCalibration", diffs[2].name);
+    assert_eq!(CellClass::Factory, diffs[2].class);
+    assert_eq!(0x0000, diffs[2].raw_a);
+    assert_eq!(0x1234, diffs[2].raw_b);
+    assert_eq!(
+        "0x0f EmissivityCalibration (factory): 0x0000 -> 0x1234, \
+         EmissivityCalibration(0) -> EmissivityCalibration(4660)",
+        diffs[2].to_string()
+    );
+}
+
+#[test]
+fn diff_decodes_filter_settings() {
+    let a = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+    let mut cells = MLX90615_CELLS;
+    cells[0x02] = 0x4439;
+    cells[0x05] = 0x0481;
+    let b = EepromImage::new(EepromLayout::Mlx90615, &cells).unwrap();
+    let diffs: Vec<_> = diff(&a, &b).unwrap().collect();
+    assert_eq!(2, diffs.len());
+    assert_eq!(CellValue::Config { iir: 1 }, diffs[0].a);
+    assert_eq!(CellValue::Config { iir: 4 }, diffs[0].b);
+    assert_eq!(CellClass::CustomerWritable, diffs[0].class);
+    assert_eq!(CellValue::Factory(0x0481), diffs[1].b);
+    assert_eq!(CellClass::Factory, diffs[1].class);
+}
+
+#[cfg(feature = "std")]
+mod intel_hex {
+    use super::*;
+    use mlx9061x::IntelHexError;
+
+    const MLX90615_HEX: &str = "\
+:100000005B35C00939140040002B8004003F0E0B03
+:100010009A1F4A8B3A0C605A000000002301674582
+:00000001FF
+";
+
+    #[test]
+    fn can_encode_intel_hex() {
+        let image = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+        assert_eq!(MLX90615_HEX, image.to_intel_hex());
+    }
+
+    #[test]
+    fn can_decode_intel_hex() {
+        let image = EepromImage::from_intel_hex(EepromLayout::Mlx90615, MLX90615_HEX).unwrap();
+        assert_eq!(EepromLayout::Mlx90615, image.layout());
+        assert_eq!(&MLX90615_CELLS, image.cells());
+    }
+
+    #[test]
+    fn intel_hex_round_trip_mlx90614() {
+        let image = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+        let decoded =
+            EepromImage::from_intel_hex(EepromLayout::Mlx90614, &image.to_intel_hex()).unwrap();
+        assert_eq!(image, decoded);
+    }
+
+    #[test]
+    fn intel_hex_requires_matching_layout() {
+        assert_eq!(
+            Err(IntelHexError::Length),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90614, MLX90615_HEX)
+        );
+        let image = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+        assert_eq!(
+            Err(IntelHexError::Length),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, &image.to_intel_hex())
+        );
+        let truncated: String = image
+            .to_intel_hex()
+            .lines()
+            .skip(2)
+            .map(|line| format!("{}\n", line))
+            .collect();
+        assert_eq!(
+            Err(IntelHexError::Length),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90614, &truncated)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_intel_hex() {
+        let bad_checksum = MLX90615_HEX.replacen("03\n", "04\n", 1);
+        assert_eq!(
+            Err(IntelHexError::Checksum(1)),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, &bad_checksum)
+        );
+        let bad_syntax = MLX90615_HEX.replacen(":10001000", "10001000", 1);
+        assert_eq!(
+            Err(IntelHexError::Syntax(2)),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, &bad_syntax)
+        );
+        assert_eq!(
+            Err(IntelHexError::Syntax(1)),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, ":0é0000000000000")
+        );
+        assert_eq!(
+            Err(IntelHexError::Syntax(1)),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, ":+0000001FF")
+        );
+        let first_line_only = MLX90615_HEX.lines().next().unwrap();
+        assert_eq!(
+            Err(IntelHexError::Length),
+            EepromImage::from_intel_hex(EepromLayout::Mlx90615, first_line_only)
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+mod structured {
+    use super::*;
+
+    #[test]
+    fn json_names_and_decodes_cells() {
+        let image = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+        let json = serde_json::to_value(&image).unwrap();
+        assert_eq!("Mlx90615", json["layout"]);
+        assert_eq!(0x0123_4567, json["device_id"]);
+        let cell = &json["cells"][3];
+        assert_eq!(3, cell["address"]);
+        assert_eq!("Emissivity", cell["name"]);
+        assert_eq!(0x4000, cell["raw"]);
+        assert_eq!(1.0, cell["value"]["Emissivity"]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let image = EepromImage::new(EepromLayout::Mlx90614, &mlx90614_cells()).unwrap();
+        let json = serde_json::to_string(&image).unwrap();
+        assert_eq!(image, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn json_requires_consecutive_cells() {
+        let image = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+        let mut json = serde_json::to_value(&image).unwrap();
+        json["cells"][2]["address"] = 7.into();
+        assert!(serde_json::from_value::<EepromImage>(json).is_err());
+    }
+
+    #[test]
+    fn json_requires_consistent_fields() {
+        let image = EepromImage::new(EepromLayout::Mlx90615, &MLX90615_CELLS).unwrap();
+        let json = serde_json::to_value(&image).unwrap();
+        let mut wrong_name = json.clone();
+        wrong_name["cells"][3]["name"] = "Config".into();
+        assert!(serde_json::from_value::<EepromImage>(wrong_name).is_err());
+        let mut wrong_value = json.clone();
+        wrong_value["cells"][3]["value"]["Emissivity"] = 0.5.into();
+        assert!(serde_json::from_value::<EepromImage>(wrong_value).is_err());
+        let mut wrong_id = json;
+        wrong_id["device_id"] = 0x0123_4568.into();
+        assert!(serde_json::from_value::<EepromImage>(wrong_id).is_err());
+    }
+}
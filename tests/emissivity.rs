@@ -0,0 +1,72 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::Error;
+
+fn mlx90615_measurement() -> Vec<I2cTrans> {
+    use mlx90615::{Register as Reg, DEV_ADDR};
+    vec![
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0x00, 0x40, 236]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::RAW_IR], vec![0x00, 0x01, 241]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TA], vec![59, 58, 4]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TOBJ], vec![193, 66, 235]),
+    ]
+}
+
+fn mlx90614_measurement() -> Vec<I2cTrans> {
+    use mlx90614::{Register as Reg, DEV_ADDR};
+    vec![
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::RAW_IR1], vec![0x00, 0x01, 59]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TA], vec![59, 58, 216]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TOBJ1], vec![193, 66, 55]),
+    ]
+}
+
+#[test]
+fn can_estimate_emissivity() {
+    let mut sensor = new_mlx90615(&mlx90615_measurement());
+    let estimate = sensor.estimate_emissivity(100.0).unwrap();
+    assert_near!(estimate.emissivity, 0.5, 0.001);
+    assert_near!(estimate.current_emissivity, 1.0, 0.001);
+    assert_near!(estimate.ambient, 24.99, 0.01);
+    assert_near!(estimate.object, 68.63, 0.01);
+    assert_eq!(0x100, estimate.raw_ir);
+    destroy(sensor);
+}
+
+#[test]
+fn estimate_emissivity_at_ambient_temperature_returns_error() {
+    let mut sensor = new_mlx90614(&mlx90614_measurement());
+    assert_error!(sensor.estimate_emissivity(24.99), InvalidInputData);
+    destroy(sensor);
+}
+
+#[test]
+fn can_program_estimated_emissivity() {
+    let mut transactions = mlx90615_measurement();
+    transactions.push(I2cTrans::write(
+        mlx90615::DEV_ADDR,
+        vec![mlx90615::Register::EMISSIVITY, 0, 0, 243],
+    ));
+    transactions.push(I2cTrans::write(
+        mlx90615::DEV_ADDR,
+        vec![mlx90615::Register::EMISSIVITY, 0x01, 0x20, 6],
+    ));
+    let mut sensor = new_mlx90615(&transactions);
+    let (estimate, _) = sensor
+        .program_estimated_emissivity(100.0, &mut NoopDelay {})
+        .unwrap();
+    assert_near!(estimate.emissivity, 0.5, 0.001);
+    destroy(sensor);
+}
+
+#[test]
+fn out_of_range_estimated_emissivity_is_not_programmed() {
+    let mut sensor = new_mlx90614(&mlx90614_measurement());
+    assert_error!(
+        sensor.program_estimated_emissivity(50.0, &mut NoopDelay {}),
+        EmissivityOutOfRange
+    );
+    destroy(sensor);
+}
@@ -0,0 +1,81 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::Error;
+
+#[test]
+fn checksum_mismatch_carries_register_and_pec() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TA],
+        vec![225, 57, 234],
+    )]);
+    match sensor.ambient_temperature() {
+        Err(Error::ChecksumMismatch {
+            register: mlx90614::Register::TA,
+            expected: 233,
+            received: 234,
+        }) => (),
+        _ => panic!("Should have returned error."),
+    }
+    destroy(sensor);
+}
+
+#[test]
+fn can_get_i2c_error_kind() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TA],
+        vec![0, 0, 0],
+    )
+    .with_error(ErrorKind::Bus)]);
+    let error = sensor.ambient_temperature().unwrap_err();
+    assert_eq!(Some(ErrorKind::Bus), error.kind());
+    destroy(sensor);
+}
+
+#[test]
+fn non_i2c_errors_have_no_kind() {
+    assert_eq!(None, Error::<ErrorKind>::MeasurementError.kind());
+}
+
+#[test]
+fn can_display_errors() {
+    let error: Error<ErrorKind> = Error::ChecksumMismatch {
+        register: 0x06,
+        expected: 0xE9,
+        received: 0xEA,
+    };
+    assert_eq!(
+        "PEC mismatch reading register 0x06: expected 0xe9, received 0xea",
+        error.to_string()
+    );
+    assert_eq!(
+        "invalid slave address 0x80",
+        Error::<ErrorKind>::InvalidAddress(0x80).to_string()
+    );
+    assert_eq!(
+        "another device answers at address 0x5c",
+        Error::<ErrorKind>::AddressInUse(0x5C).to_string()
+    );
+}
+
+#[derive(Debug)]
+struct BusError;
+
+impl core::fmt::Display for BusError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bus error")
+    }
+}
+
+impl core::error::Error for BusError {}
+
+#[test]
+fn i2c_error_is_the_source() {
+    use core::error::Error as _;
+    let error = Error::I2C(BusError);
+    assert_eq!("bus error", error.source().unwrap().to_string());
+    assert!(Error::<BusError>::NotSupported.source().is_none());
+}
@@ -1,11 +1,16 @@
 mod base;
-use crate::base::{destroy, mlx90614, mlx90614::Register as Reg, new_mlx90614};
+use crate::base::{
+    destroy, mlx90614,
+    mlx90614::{Register as Reg, DEV_ADDR},
+    new_mlx90614, new_mlx90614_dual_zone,
+};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
 use embedded_hal_mock::eh1::{
     delay::NoopDelay,
     i2c::Transaction as I2cTrans,
     pin::{Mock as PinMock, State as PinState, Transaction as PinTrans},
 };
-use mlx9061x::{wake_mlx90614, SlaveAddr};
+use mlx9061x::{wake_mlx90614, EmissivityCalibration, Error, Material, SlaveAddr};
 
 macro_rules! read_f32_test {
     ($name:ident, $method:ident, $reg:expr, $data0:expr, $data1:expr, $data2:expr, $expected:expr) => {
@@ -37,8 +42,10 @@ read_f32_test!(
     24.57
 );
 
-read_f32_test!(
+read_f32_test_base!(
     read_object2_temp,
+    new_mlx90614_dual_zone,
+    mlx90614::DEV_ADDR,
     object2_temperature,
     Reg::TOBJ2,
     38,
@@ -47,6 +54,17 @@ read_f32_test!(
     24.57
 );
 
+#[test]
+fn read_object1_temp_with_error_flag_returns_error() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::TOBJ1],
+        vec![0x00, 0x80, 143],
+    )]);
+    assert_error!(sensor.object1_temperature(), MeasurementError);
+    destroy(sensor);
+}
+
 read_u16_test!(
     read_ta_as_int,
     new_mlx90614,
@@ -73,7 +91,7 @@ read_u16_test!(
 
 read_u16_test!(
     read_object2_temp_as_int,
-    new_mlx90614,
+    new_mlx90614_dual_zone,
     mlx90614::DEV_ADDR,
     object2_temperature_as_int,
     Reg::TOBJ2,
@@ -97,7 +115,7 @@ read_i16_test!(
 
 read_i16_test!(
     read_raw_ir2,
-    new_mlx90614,
+    new_mlx90614_dual_zone,
     mlx90614::DEV_ADDR,
     raw_ir_channel2,
     Reg::RAW_IR2,
@@ -107,25 +125,163 @@ read_i16_test!(
     0x3A26
 );
 
+#[test]
+fn object2_is_not_supported_on_single_zone_parts() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Reg::CONFIG1],
+        vec![0xB4, 0x9F, 43],
+    )]);
+    assert_error!(sensor.object2_temperature(), NotSupported);
+    assert_error!(sensor.object2_temperature_as_int(), NotSupported);
+    assert_error!(sensor.raw_ir_channel2(), NotSupported);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_dual_zone_temperature() {
+    let mut sensor = new_mlx90614_dual_zone(&[
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TOBJ1], vec![38, 58, 112]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::TOBJ2], vec![193, 66, 229]),
+    ]);
+    let reading = sensor.dual_zone_temperature().unwrap();
+    assert_near!(reading.object1, 24.57, 0.01);
+    assert_near!(reading.object2, 68.63, 0.01);
+    assert_near!(reading.difference(), -44.06, 0.02);
+    assert_near!(reading.average(), 46.6, 0.02);
+    destroy(sensor);
+}
+
 #[test]
 fn can_change_address() {
     let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(0x5C, vec![Reg::TA], vec![0, 0, 0])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
         I2cTrans::write(mlx90614::DEV_ADDR, vec![Reg::ADDRESS, 0, 0, 175]),
         I2cTrans::write(mlx90614::DEV_ADDR, vec![Reg::ADDRESS, 0x5C, 0, 95]),
+        I2cTrans::write_read(mlx90614::DEV_ADDR, vec![Reg::TA], vec![225, 57, 233]),
+        I2cTrans::write_read(0x5C, vec![Reg::TA], vec![225, 57, 133]),
     ]);
-    sensor
+    let change = sensor
         .set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {})
         .unwrap();
+    assert_eq!(mlx90614::DEV_ADDR, change.old_address());
+    assert_eq!(0x5C, change.new_address());
+    assert_eq!(0, sensor.bus_stats().i2c_errors);
+    sensor.ambient_temperature().unwrap();
+    sensor.confirm_address_change(change);
+    sensor.ambient_temperature().unwrap();
     destroy(sensor);
 }
 
 #[test]
 fn can_set_emissivity() {
     let mut sensor = new_mlx90614(&[
-        I2cTrans::write(mlx90614::DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
-        I2cTrans::write(mlx90614::DEV_ADDR, vec![Reg::EMISSIVITY, 51, 179, 254]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Reg::EMISSIVITY_CALIBRATION],
+            vec![0, 0x40, 191],
+        ),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 51, 179, 254]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![51, 179, 36]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 0, 0, 196]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 109, 91, 94]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Reg::EMISSIVITY_CALIBRATION],
+            vec![109, 91, 226],
+        ),
+    ]);
+    let previous = sensor.set_emissivity(0.7, &mut NoopDelay {}).unwrap();
+    assert_eq!(
+        EmissivityCalibration {
+            emissivity: 0xFFFF,
+            calibration: 0x4000
+        },
+        previous
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn set_emissivity_verification_mismatch_returns_error() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Reg::EMISSIVITY_CALIBRATION],
+            vec![0, 0x40, 191],
+        ),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 51, 179, 254]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0, 0, 242]),
+    ]);
+    assert_error!(
+        sensor.set_emissivity(0.7, &mut NoopDelay {}),
+        EepromVerificationFailed
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn set_emissivity_calibration_mismatch_restores_emissivity() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Reg::EMISSIVITY_CALIBRATION],
+            vec![0, 0x40, 191],
+        ),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 51, 179, 254]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![51, 179, 36]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 0, 0, 196]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 109, 91, 94]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION], vec![0, 0, 120]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0xFF, 0xFF, 12]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+    ]);
+    assert_error!(
+        sensor.set_emissivity(0.7, &mut NoopDelay {}),
+        EepromVerificationFailed
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn set_emissivity_for_too_reflective_material_returns_error() {
+    let mut sensor = new_mlx90614(&[]);
+    assert_error!(
+        sensor.set_emissivity_for(Material::PolishedAluminium, &mut NoopDelay {}),
+        EmissivityOutOfRange
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_restore_emissivity() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 40]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0xFF, 0xFF, 12]),
+        I2cTrans::write_read(DEV_ADDR, vec![Reg::EMISSIVITY], vec![0xFF, 0xFF, 214]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 0, 0, 196]),
+        I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY_CALIBRATION, 0, 0x40, 3]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Reg::EMISSIVITY_CALIBRATION],
+            vec![0, 0x40, 191],
+        ),
     ]);
-    sensor.set_emissivity(0.7, &mut NoopDelay {}).unwrap();
+    let previous = EmissivityCalibration {
+        emissivity: 0xFFFF,
+        calibration: 0x4000,
+    };
+    sensor
+        .restore_emissivity(previous, &mut NoopDelay {})
+        .unwrap();
     destroy(sensor);
 }
 
@@ -1,11 +1,12 @@
 mod base;
 use crate::base::{destroy, mlx90615, mlx90615::Register as Reg, new_mlx90615};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
 use embedded_hal_mock::eh1::{
     delay::NoopDelay,
     i2c::Transaction as I2cTrans,
     pin::{Mock as PinMock, State as PinState, Transaction as PinTrans},
 };
-use mlx9061x::{wake_mlx90615, SlaveAddr};
+use mlx9061x::{wake_mlx90615, Error, Material, SlaveAddr};
 
 macro_rules! read_f32_test {
     ($name:ident, $method:ident, $reg:expr, $data0:expr, $data1:expr, $data2:expr, $expected:expr) => {
@@ -37,6 +38,17 @@ read_f32_test!(
     24.57
 );
 
+#[test]
+fn read_object_temp_with_error_flag_returns_error() {
+    let mut sensor = new_mlx90615(&[I2cTrans::write_read(
+        mlx90615::DEV_ADDR,
+        vec![Reg::TOBJ],
+        vec![0x00, 0x80, 83],
+    )]);
+    assert_error!(sensor.object_temperature(), MeasurementError);
+    destroy(sensor);
+}
+
 read_u16_test!(
     read_object_temp_as_int,
     new_mlx90615,
@@ -76,12 +88,21 @@ read_i16_test!(
 #[test]
 fn can_change_address() {
     let mut sensor = new_mlx90615(&[
+        I2cTrans::write_read(0x5C, vec![Reg::TA], vec![0, 0, 0])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
         I2cTrans::write(mlx90615::DEV_ADDR, vec![Reg::ADDRESS, 0, 0, 78]),
         I2cTrans::write(mlx90615::DEV_ADDR, vec![Reg::ADDRESS, 0x5C, 0, 190]),
+        I2cTrans::write_read(mlx90615::DEV_ADDR, vec![Reg::TA], vec![225, 57, 53]),
+        I2cTrans::write_read(0x5C, vec![Reg::TA], vec![225, 57, 75]),
     ]);
-    sensor
+    let change = sensor
         .set_address(SlaveAddr::Alternative(0x5C), &mut NoopDelay {})
         .unwrap();
+    assert_eq!(mlx90615::DEV_ADDR, change.old_address());
+    assert_eq!(0x5C, change.new_address());
+    sensor.ambient_temperature().unwrap();
+    sensor.confirm_address_change(change);
+    sensor.ambient_temperature().unwrap();
     destroy(sensor);
 }
 
@@ -95,6 +116,18 @@ fn can_set_emissivity() {
     destroy(sensor);
 }
 
+#[test]
+fn can_set_emissivity_for_material() {
+    let mut sensor = new_mlx90615(&[
+        I2cTrans::write(mlx90615::DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, 243]),
+        I2cTrans::write(mlx90615::DEV_ADDR, vec![Reg::EMISSIVITY, 0xB8, 0x3E, 174]),
+    ]);
+    sensor
+        .set_emissivity_for(Material::HumanSkin, &mut NoopDelay {})
+        .unwrap();
+    destroy(sensor);
+}
+
 read_f32_test!(read_emiss, emissivity, Reg::EMISSIVITY, 205, 44, 235, 0.7);
 
 #[test]
@@ -0,0 +1,75 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614_dual_zone};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{IrModel, IrModelFit, IrSample};
+
+#[test]
+fn out_of_range_emissivity_is_rejected() {
+    assert!(IrModel::new(1.0, 0.0).is_none());
+    assert!(IrModel::new(1.0, 1.1).is_none());
+}
+
+#[test]
+fn can_compute_object_temperature() {
+    let model = IrModel::new(22_439_177.0, 1.0).unwrap();
+    assert_near!(model.object_temperature(256.0, 24.99), 68.63, 0.01);
+    assert_near!(model.object_temperature(0.0, 24.99), 24.99, 0.01);
+}
+
+#[test]
+fn emissivity_scales_radiation() {
+    let model = IrModel::new(22_439_177.0, 1.0)
+        .unwrap()
+        .with_emissivity(0.5)
+        .unwrap();
+    assert_near!(model.object_temperature(128.0, 24.99), 68.63, 0.01);
+}
+
+#[test]
+fn fit_without_samples_fails() {
+    assert!(IrModelFit::new(1.0).model().is_none());
+}
+
+#[test]
+fn can_fit_model() {
+    let mut fit = IrModelFit::new(1.0);
+    fit.add_sample(&IrSample {
+        raw_ir: 256,
+        ambient: 24.99,
+        object: 68.63,
+    });
+    fit.add_sample(&IrSample {
+        raw_ir: 0,
+        ambient: 24.99,
+        object: 24.99,
+    });
+    let model = fit.model().unwrap();
+    assert_near!(model.sensitivity(), 22_439_177.0, 5000.0);
+    assert_near!(model.emissivity(), 1.0, 0.0001);
+}
+
+#[test]
+fn can_read_ir_sample_channel2() {
+    let mut sensor = new_mlx90614_dual_zone(&[
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::RAW_IR2],
+            vec![0x00, 0x01, 45],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![59, 58, 216],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ2],
+            vec![193, 66, 229],
+        ),
+    ]);
+    let sample = sensor.ir_sample_channel2().unwrap();
+    assert_eq!(0x100, sample.raw_ir);
+    assert_near!(sample.ambient, 24.99, 0.01);
+    assert_near!(sample.object, 68.63, 0.01);
+    destroy(sensor);
+}
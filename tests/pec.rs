@@ -0,0 +1,81 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{BusStats, Error, PecMode};
+
+fn ta(data: [u8; 3]) -> I2cTrans {
+    I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TA],
+        data.to_vec(),
+    )
+}
+
+#[test]
+fn pec_is_strict_by_default() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 234])]);
+    assert_eq!(PecMode::Strict, sensor.pec_mode());
+    assert_crc_mismatch!(sensor.ambient_temperature());
+    assert_eq!(
+        BusStats {
+            pec_failures: 1,
+            retries: 0,
+            i2c_errors: 0
+        },
+        sensor.bus_stats()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_retry_on_pec_mismatch() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 234]), ta([225, 57, 233])]);
+    sensor.set_pec_mode(PecMode::Retry(2));
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.1);
+    assert_eq!(
+        BusStats {
+            pec_failures: 1,
+            retries: 1,
+            i2c_errors: 0
+        },
+        sensor.bus_stats()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn retries_are_limited() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 234]), ta([225, 57, 235])]);
+    sensor.set_pec_mode(PecMode::Retry(1));
+    assert_crc_mismatch!(sensor.ambient_temperature());
+    assert_eq!(2, sensor.bus_stats().pec_failures);
+    assert_eq!(1, sensor.bus_stats().retries);
+    destroy(sensor);
+}
+
+#[test]
+fn can_disable_pec() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 0])]);
+    sensor.set_pec_mode(PecMode::Disabled);
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.1);
+    assert_eq!(BusStats::default(), sensor.bus_stats());
+    destroy(sensor);
+}
+
+#[test]
+fn counts_i2c_errors_and_can_reset() {
+    let mut sensor = new_mlx90614(&[ta([0, 0, 0]).with_error(ErrorKind::Other)]);
+    sensor.set_pec_mode(PecMode::Retry(3));
+    match sensor.ambient_temperature() {
+        Err(Error::I2C(ErrorKind::Other)) => (),
+        _ => panic!("Should have returned error."),
+    }
+    assert_eq!(1, sensor.bus_stats().i2c_errors);
+    assert_eq!(0, sensor.bus_stats().retries);
+    sensor.reset_bus_stats();
+    assert_eq!(BusStats::default(), sensor.bus_stats());
+    destroy(sensor);
+}
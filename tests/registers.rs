@@ -0,0 +1,204 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::{
+    registers::{mlx90614 as regs14, mlx90615 as regs15},
+    unlock_factory_cells, Error,
+};
+
+#[test]
+fn can_read_ram() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TOBJ1],
+        vec![38, 58, 112],
+    )]);
+    assert_eq!(0x3A26, sensor.read_ram(regs14::RamRegister::Tobj1).unwrap());
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_eeprom() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::ID0],
+        vec![0x34, 0x12, 246],
+    )]);
+    assert_eq!(
+        0x1234,
+        sensor.read_eeprom(regs14::EepromRegister::Id(0)).unwrap()
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_write_eeprom() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::CONFIG1, 0, 0, 67],
+        ),
+        I2cTrans::write(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::CONFIG1, 0xB4, 0x9F, 140],
+        ),
+    ]);
+    sensor
+        .write_eeprom(regs14::EepromRegister::Config1, 0x9FB4, &mut NoopDelay {})
+        .unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn cannot_write_factory_cells_without_unlock() {
+    let mut sensor = new_mlx90614(&[]);
+    assert_error!(
+        sensor.write_eeprom(
+            regs14::EepromRegister::EmissivityCalibration,
+            0,
+            &mut NoopDelay {}
+        ),
+        ProtectedCell
+    );
+    assert_error!(
+        sensor.write_eeprom(regs14::EepromRegister::Factory(0x08), 0, &mut NoopDelay {}),
+        ProtectedCell
+    );
+    destroy(sensor);
+
+    let mut sensor = new_mlx90615(&[]);
+    assert_error!(
+        sensor.write_eeprom(regs15::EepromRegister::Id(1), 0, &mut NoopDelay {}),
+        ProtectedCell
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_write_factory_cells_with_unlock() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write(mlx90614::DEV_ADDR, vec![0x28, 0, 0, 210]),
+        I2cTrans::write(mlx90614::DEV_ADDR, vec![0x28, 0x34, 0x12, 1]),
+    ]);
+    sensor
+        .write_eeprom_unprotected(
+            regs14::EepromRegister::Factory(0x08),
+            0x1234,
+            unlock_factory_cells(),
+            &mut NoopDelay {},
+        )
+        .unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn read_only_cells_cannot_be_written_even_unprotected() {
+    let mut sensor = new_mlx90614(&[]);
+    assert_error!(
+        sensor.write_eeprom_unprotected(
+            regs14::EepromRegister::Id(2),
+            0,
+            unlock_factory_cells(),
+            &mut NoopDelay {}
+        ),
+        ProtectedCell
+    );
+    destroy(sensor);
+
+    let mut sensor = new_mlx90615(&[]);
+    assert_error!(
+        sensor.write_eeprom_unprotected(
+            regs15::EepromRegister::Id(0),
+            0,
+            unlock_factory_cells(),
+            &mut NoopDelay {}
+        ),
+        ProtectedCell
+    );
+    destroy(sensor);
+}
+
+#[test]
+fn can_query_protection_maps() {
+    use regs14::EepromRegister as Eeprom14;
+    assert!(Eeprom14::Emissivity.is_customer_writable());
+    assert!(Eeprom14::Address.is_customer_writable());
+    assert!(!Eeprom14::EmissivityCalibration.is_customer_writable());
+    assert!(!Eeprom14::Factory(0x06).is_customer_writable());
+    assert!(!Eeprom14::Factory(0x1B).is_read_only());
+    assert!(Eeprom14::Id(0).is_read_only());
+    assert!(Eeprom14::Factory(0x1D).is_read_only());
+
+    use regs15::EepromRegister as Eeprom15;
+    assert!(Eeprom15::Config.is_customer_writable());
+    assert!(!Eeprom15::Factory(0x04).is_customer_writable());
+    assert!(!Eeprom15::Factory(0x04).is_read_only());
+    assert!(Eeprom15::Id(1).is_read_only());
+}
+
+#[test]
+fn can_access_mlx90615_registers() {
+    let mut sensor = new_mlx90615(&[
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::CONFIG],
+            vec![0x00, 0x10, 77],
+        ),
+        I2cTrans::write(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::EMISSIVITY, 0, 0, 243],
+        ),
+        I2cTrans::write(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::EMISSIVITY, 0x00, 0x40, 52],
+        ),
+    ]);
+    assert_eq!(
+        0x1000,
+        sensor.read_eeprom(regs15::EepromRegister::Config).unwrap()
+    );
+    sensor
+        .write_eeprom(
+            regs15::EepromRegister::Emissivity,
+            0x4000,
+            &mut NoopDelay {},
+        )
+        .unwrap();
+    destroy(sensor);
+}
+
+#[test]
+fn mlx90614_commands_match_datasheet() {
+    use regs14::{EepromRegister as Eeprom, RamRegister as Ram};
+    assert_eq!(0x04, Ram::RawIr1.command());
+    assert_eq!(0x05, Ram::RawIr2.command());
+    assert_eq!(0x06, Ram::Ta.command());
+    assert_eq!(0x07, Ram::Tobj1.command());
+    assert_eq!(0x08, Ram::Tobj2.command());
+    assert_eq!(0x20, Eeprom::ToMax.command());
+    assert_eq!(0x21, Eeprom::ToMin.command());
+    assert_eq!(0x22, Eeprom::PwmCtrl.command());
+    assert_eq!(0x23, Eeprom::TaRange.command());
+    assert_eq!(0x24, Eeprom::Emissivity.command());
+    assert_eq!(0x25, Eeprom::Config1.command());
+    assert_eq!(0x2E, Eeprom::Address.command());
+    assert_eq!(0x2F, Eeprom::EmissivityCalibration.command());
+    assert_eq!(0x3C, Eeprom::Id(0).command());
+    assert_eq!(0x3F, Eeprom::Id(3).command());
+    assert_eq!(0x30, Eeprom::Factory(0x10).command());
+}
+
+#[test]
+fn mlx90615_commands_match_datasheet() {
+    use regs15::{EepromRegister as Eeprom, RamRegister as Ram};
+    assert_eq!(0x25, Ram::RawIr.command());
+    assert_eq!(0x26, Ram::Ta.command());
+    assert_eq!(0x27, Ram::Tobj.command());
+    assert_eq!(0x10, Eeprom::Address.command());
+    assert_eq!(0x11, Eeprom::PwmRange.command());
+    assert_eq!(0x12, Eeprom::Config.command());
+    assert_eq!(0x13, Eeprom::Emissivity.command());
+    assert_eq!(0x1E, Eeprom::Id(0).command());
+    assert_eq!(0x1F, Eeprom::Id(1).command());
+    assert_eq!(0x14, Eeprom::Factory(0x04).command());
+}
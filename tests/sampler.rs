@@ -0,0 +1,85 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::{Error, Reducer, Sampler};
+
+fn tobj1(data: [u8; 3]) -> I2cTrans {
+    I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TOBJ1],
+        data.to_vec(),
+    )
+}
+
+const T24_57: [u8; 3] = [38, 58, 112];
+const T23_19: [u8; 3] = [225, 57, 255];
+const T25_75: [u8; 3] = [97, 58, 64];
+const T25_95: [u8; 3] = [107, 58, 194];
+const PEC_FAILURE: [u8; 3] = [38, 58, 113];
+const ERROR_FLAG: [u8; 3] = [0x00, 0x80, 143];
+
+#[test]
+fn median_skips_invalid_readings() {
+    let sensor = new_mlx90614(&[
+        tobj1(T24_57),
+        tobj1(PEC_FAILURE),
+        tobj1(T23_19),
+        tobj1(ERROR_FLAG),
+        tobj1(T25_75),
+        tobj1(T25_95),
+    ]);
+    let mut sampler: Sampler<_, _, 6> = Sampler::new(sensor, Reducer::Median, 10);
+    let sample = sampler.object1_temperature(&mut NoopDelay {}).unwrap();
+    assert_eq!(4, sample.count);
+    assert_near!(sample.value, 25.16, 0.01);
+    assert_near!(sample.spread, 2.76, 0.01);
+    destroy(sampler.destroy());
+}
+
+#[test]
+fn can_compute_mean() {
+    let sensor = new_mlx90614(&[tobj1(T24_57), tobj1(T23_19), tobj1(T25_75)]);
+    let mut sampler: Sampler<_, _, 3> = Sampler::new(sensor, Reducer::Mean, 10);
+    let sample = sampler.object1_temperature(&mut NoopDelay {}).unwrap();
+    assert_eq!(3, sample.count);
+    assert_near!(sample.value, 24.503, 0.01);
+    destroy(sampler.destroy());
+}
+
+#[test]
+fn can_compute_trimmed_mean() {
+    let sensor = new_mlx90614(&[tobj1(T25_95), tobj1(T23_19), tobj1(T24_57), tobj1(T25_75)]);
+    let mut sampler: Sampler<_, _, 4> = Sampler::new(sensor, Reducer::TrimmedMean(1), 10);
+    let sample = sampler.object1_temperature(&mut NoopDelay {}).unwrap();
+    assert_eq!(4, sample.count);
+    assert_near!(sample.value, 25.16, 0.01);
+    destroy(sampler.destroy());
+}
+
+#[test]
+fn returns_last_error_without_valid_readings() {
+    let sensor = new_mlx90614(&[tobj1(PEC_FAILURE), tobj1(ERROR_FLAG)]);
+    let mut sampler: Sampler<_, _, 2> = Sampler::new(sensor, Reducer::Mean, 10);
+    assert_error!(
+        sampler.object1_temperature(&mut NoopDelay {}),
+        MeasurementError
+    );
+    destroy(sampler.destroy());
+}
+
+#[test]
+fn returns_i2c_errors_immediately() {
+    let sensor = new_mlx90615(&[I2cTrans::write_read(
+        mlx90615::DEV_ADDR,
+        vec![mlx90615::Register::TA],
+        vec![0, 0, 0],
+    )
+    .with_error(ErrorKind::Other)]);
+    let mut sampler: Sampler<_, _, 3> = Sampler::new(sensor, Reducer::Mean, 10);
+    match sampler.ambient_temperature(&mut NoopDelay {}) {
+        Err(Error::I2C(ErrorKind::Other)) => (),
+        _ => panic!("Should have returned error."),
+    }
+    destroy(sampler.destroy());
+}
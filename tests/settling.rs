@@ -0,0 +1,83 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::Error;
+
+fn mlx90614_config(data: [u8; 3]) -> I2cTrans {
+    I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::CONFIG1],
+        data.to_vec(),
+    )
+}
+
+#[test]
+fn can_get_default_data_ready_delay() {
+    let mut sensor = new_mlx90614(&[mlx90614_config([0xB4, 0x9F, 43])]);
+    assert_eq!(250, sensor.data_ready_delay_ms().unwrap());
+    destroy(sensor);
+}
+
+#[test]
+fn data_ready_delay_depends_on_filters() {
+    let mut sensor = new_mlx90614(&[mlx90614_config([0xB0, 0x9C, 118])]);
+    assert_eq!(156, sensor.data_ready_delay_ms().unwrap());
+    destroy(sensor);
+}
+
+#[test]
+fn slowest_iir_filter_needs_23_cycles() {
+    let mut sensor = new_mlx90614(&[mlx90614_config([0xB3, 0x9F, 64])]);
+    assert_eq!(23 * 250, sensor.data_ready_delay_ms().unwrap());
+    destroy(sensor);
+}
+
+#[test]
+fn data_is_ready_without_power_on() {
+    let mut sensor = new_mlx90614(&[]);
+    assert!(sensor.is_data_ready(0));
+    destroy(sensor);
+}
+
+#[test]
+fn readings_can_require_data_ready() {
+    let mut sensor = new_mlx90614(&[
+        mlx90614_config([0xB4, 0x9F, 43]),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![225, 57, 233],
+        ),
+    ]);
+    sensor.set_require_data_ready(true);
+    sensor.mark_power_on(1000).unwrap();
+    assert_error!(sensor.ambient_temperature(), DataNotReady);
+    assert!(!sensor.is_data_ready(1100));
+    assert!(!sensor.is_data_ready(1200));
+    assert!(sensor.is_data_ready(1250));
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.1);
+    destroy(sensor);
+}
+
+#[test]
+fn can_wait_data_ready() {
+    let mut sensor = new_mlx90615(&[
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::CONFIG],
+            vec![0x00, 0x10, 77],
+        ),
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::TOBJ],
+            vec![193, 66, 235],
+        ),
+    ]);
+    sensor.set_require_data_ready(true);
+    sensor.mark_power_on(0).unwrap();
+    sensor.wait_data_ready(100, &mut NoopDelay {});
+    let t = sensor.object_temperature().unwrap();
+    assert_near!(t, 68.63, 0.01);
+    destroy(sensor);
+}
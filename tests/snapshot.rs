@@ -0,0 +1,110 @@
+mod base;
+use crate::base::{
+    destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90614_dual_zone, new_mlx90615,
+};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+
+fn reads(address: u8, reads: &[(u8, [u8; 3])]) -> Vec<I2cTrans> {
+    reads
+        .iter()
+        .map(|(register, data)| I2cTrans::write_read(address, vec![*register], data.to_vec()))
+        .collect()
+}
+
+#[test]
+fn can_read_dual_zone_snapshot() {
+    use mlx90614::Register as Reg;
+    let mut sensor = new_mlx90614_dual_zone(&reads(
+        mlx90614::DEV_ADDR,
+        &[
+            (Reg::TA, [225, 57, 233]),
+            (Reg::RAW_IR1, [0x26, 0x3A, 0x4A]),
+            (Reg::RAW_IR2, [0x26, 0x3A, 0x5C]),
+            (Reg::TOBJ1, [0x00, 0x80, 143]),
+            (Reg::TOBJ2, [38, 58, 161]),
+            (Reg::TA, [225, 57, 233]),
+            (Reg::TOBJ1, [0x00, 0x80, 143]),
+        ],
+    ));
+    let snapshot = sensor.snapshot().unwrap();
+    assert_near!(snapshot.ambient.value, 23.19, 0.01);
+    assert!(snapshot.ambient.valid);
+    assert_eq!(0x3A26, snapshot.raw_ir1.value);
+    assert_eq!(0x3A26, snapshot.raw_ir2.unwrap().raw);
+    assert_eq!(0x8000, snapshot.object1.raw);
+    assert!(!snapshot.object1.valid);
+    let object2 = snapshot.object2.unwrap();
+    assert_near!(object2.value, 24.57, 0.01);
+    assert!(!object2.valid);
+    assert!(snapshot.consistent);
+    assert_eq!(1, sensor.bus_stats().pec_failures);
+    destroy(sensor);
+}
+
+#[test]
+fn single_zone_snapshot_skips_channel2() {
+    use mlx90614::Register as Reg;
+    let mut transactions = vec![I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![Reg::CONFIG1],
+        vec![0xB4, 0x9F, 43],
+    )];
+    transactions.extend(reads(
+        mlx90614::DEV_ADDR,
+        &[
+            (Reg::TA, [225, 57, 233]),
+            (Reg::RAW_IR1, [0x26, 0x3A, 0x4A]),
+            (Reg::TOBJ1, [38, 58, 112]),
+            (Reg::TA, [97, 58, 86]),
+            (Reg::TOBJ1, [38, 58, 112]),
+        ],
+    ));
+    let mut sensor = new_mlx90614(&transactions);
+    let snapshot = sensor.snapshot().unwrap();
+    assert_near!(snapshot.object1.value, 24.57, 0.01);
+    assert!(snapshot.object1.valid);
+    assert_eq!(None, snapshot.object2);
+    assert_eq!(None, snapshot.raw_ir2);
+    assert!(!snapshot.consistent);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_mlx90615_snapshot() {
+    use mlx90615::Register as Reg;
+    let mut sensor = new_mlx90615(&reads(
+        mlx90615::DEV_ADDR,
+        &[
+            (Reg::TA, [225, 57, 53]),
+            (Reg::RAW_IR, [0x26, 0x3A, 128]),
+            (Reg::TOBJ, [193, 66, 235]),
+            (Reg::TA, [225, 57, 53]),
+            (Reg::TOBJ, [193, 66, 235]),
+        ],
+    ));
+    let snapshot = sensor.snapshot().unwrap();
+    assert_near!(snapshot.ambient.value, 23.19, 0.01);
+    assert_eq!(0x3A26, snapshot.raw_ir1.value);
+    assert!(snapshot.raw_ir1.valid);
+    assert_near!(snapshot.object1.value, 68.63, 0.01);
+    assert!(snapshot.object1.valid);
+    assert!(snapshot.consistent);
+    destroy(sensor);
+}
+
+#[test]
+fn object_update_makes_snapshot_inconsistent() {
+    use mlx90615::Register as Reg;
+    let mut sensor = new_mlx90615(&reads(
+        mlx90615::DEV_ADDR,
+        &[
+            (Reg::TA, [225, 57, 53]),
+            (Reg::RAW_IR, [0x26, 0x3A, 128]),
+            (Reg::TOBJ, [193, 66, 235]),
+            (Reg::TA, [225, 57, 53]),
+            (Reg::TOBJ, [194, 66, 212]),
+        ],
+    ));
+    assert!(!sensor.snapshot().unwrap().consistent);
+    destroy(sensor);
+}
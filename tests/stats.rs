@@ -0,0 +1,124 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{Error, RawStats, RunningStats};
+
+#[test]
+fn empty_stats_have_no_snapshot() {
+    assert!(RunningStats::new().snapshot().is_none());
+}
+
+#[test]
+fn single_value_has_no_deviation() {
+    let mut stats = RunningStats::new();
+    stats.update(24.57);
+    let snapshot = stats.snapshot().unwrap();
+    assert_eq!(1, snapshot.count);
+    assert_near!(snapshot.mean, 24.57, 0.0001);
+    assert_near!(snapshot.std_dev, 0.0, 0.0001);
+}
+
+#[test]
+fn can_accumulate_values() {
+    let mut stats = RunningStats::new();
+    for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        stats.update(value);
+    }
+    let snapshot = stats.snapshot().unwrap();
+    assert_eq!(8, snapshot.count);
+    assert_near!(snapshot.min, 2.0, 0.0001);
+    assert_near!(snapshot.max, 9.0, 0.0001);
+    assert_near!(snapshot.mean, 5.0, 0.0001);
+    assert_near!(snapshot.std_dev, 2.138, 0.001);
+}
+
+#[test]
+fn take_resets_stats() {
+    let mut stats = RunningStats::new();
+    stats.update(-5.0);
+    stats.update(5.0);
+    let snapshot = stats.take().unwrap();
+    assert_near!(snapshot.min, -5.0, 0.0001);
+    assert_eq!(0, stats.count());
+    assert!(stats.snapshot().is_none());
+}
+
+#[test]
+fn can_accumulate_raw_values() {
+    let mut stats = RawStats::new();
+    assert!(stats.snapshot().is_none());
+    for value in [1002, 1004, 1004, 1004, 1005, 1005, 1007, 1009] {
+        stats.update(value);
+    }
+    let snapshot = stats.snapshot().unwrap();
+    assert_eq!(8, snapshot.count);
+    assert_eq!(1002, snapshot.min);
+    assert_eq!(1009, snapshot.max);
+    assert_eq!(1005 * 256, snapshot.mean_q8);
+    assert_eq!(32 * 256 / 7, snapshot.variance_q8);
+    let celsius = snapshot.to_celsius();
+    assert_near!(celsius.mean, 1005.0 * 0.02 - 273.15, 0.0001);
+    assert_near!(celsius.std_dev, 2.138 * 0.02, 0.0001);
+    assert_eq!(8, stats.take().unwrap().count);
+    assert_eq!(0, stats.count());
+}
+
+#[test]
+fn raw_mean_is_rounded_to_fixed_point() {
+    let mut stats = RawStats::new();
+    stats.update(14886);
+    stats.update(14887);
+    stats.update(14887);
+    let snapshot = stats.snapshot().unwrap();
+    assert_eq!(14886 * 256 + 171, snapshot.mean_q8);
+}
+
+#[test]
+fn tracked_reads_accumulate_valid_readings() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![38, 58, 112],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![0x00, 0x80, 143],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![225, 57, 233],
+        ),
+    ]);
+    let mut object = RawStats::new();
+    let mut ambient = RawStats::new();
+    let t = sensor.track_object1_temperature(&mut object).unwrap();
+    assert_near!(t, 24.57, 0.01);
+    assert_error!(
+        sensor.track_object1_temperature(&mut object),
+        MeasurementError
+    );
+    sensor.track_ambient_temperature(&mut ambient).unwrap();
+
+    let raw = object.snapshot().unwrap();
+    assert_eq!(1, raw.count);
+    assert_eq!(0x3A26, raw.min);
+    assert_near!(raw.to_celsius().mean, 24.57, 0.01);
+    assert_eq!(1, ambient.count());
+    destroy(sensor);
+}
+
+#[test]
+fn object2_tracking_requires_dual_zone() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::CONFIG1],
+        vec![0xB4, 0x9F, 43],
+    )]);
+    let mut stats = RawStats::new();
+    assert_error!(sensor.track_object2_temperature(&mut stats), NotSupported);
+    assert_eq!(0, stats.count());
+    destroy(sensor);
+}
@@ -0,0 +1,111 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Transaction as I2cTrans};
+use mlx9061x::{ObjectReading, Stability, ThermalMonitor};
+
+fn ta(data: [u8; 3]) -> I2cTrans {
+    I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TA],
+        data.to_vec(),
+    )
+}
+
+fn clock(times_ms: &[u32]) -> impl FnMut() -> u32 {
+    let mut times_ms = times_ms.to_vec();
+    move || times_ms.remove(0)
+}
+
+#[test]
+fn unknown_slope_is_unstable() {
+    let mut monitor = ThermalMonitor::new(0.5);
+    assert_eq!(Stability::Unstable, monitor.update(25.0, 0));
+    assert_eq!(None, monitor.slope());
+}
+
+#[test]
+fn can_compute_slope() {
+    let mut monitor = ThermalMonitor::new(0.5);
+    monitor.update(25.0, 0);
+    assert_eq!(Stability::Unstable, monitor.update(26.0, 500));
+    assert_near!(monitor.slope().unwrap(), 2.0, 0.0001);
+    assert_eq!(Stability::Stable, monitor.update(26.2, 1500));
+    assert_eq!(ObjectReading::Stable(30.0), monitor.classify(30.0));
+}
+
+#[test]
+fn timestamps_can_wrap_around() {
+    let mut monitor = ThermalMonitor::new(0.5);
+    monitor.update(25.0, u32::MAX - 499);
+    monitor.update(24.0, 500);
+    assert_near!(monitor.slope().unwrap(), -1.0, 0.0001);
+    assert_eq!(ObjectReading::Unstable(30.0), monitor.classify(30.0));
+}
+
+#[test]
+fn can_wait_until_stable() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 233]), ta([97, 58, 86]), ta([107, 58, 212])]);
+    let mut monitor = ThermalMonitor::new(0.5);
+    let now_ms = clock(&[0, 1000, 2000]);
+    let stable = sensor
+        .wait_until_stable(&mut monitor, &mut NoopDelay {}, now_ms, 1000, 5000)
+        .unwrap();
+    assert!(stable);
+    destroy(sensor);
+}
+
+#[test]
+fn wait_until_stable_can_time_out() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 233]), ta([97, 58, 86])]);
+    let mut monitor = ThermalMonitor::new(0.5);
+    let now_ms = clock(&[0, 1000]);
+    let stable = sensor
+        .wait_until_stable(&mut monitor, &mut NoopDelay {}, now_ms, 1000, 1000)
+        .unwrap();
+    assert!(!stable);
+    destroy(sensor);
+}
+
+#[test]
+fn wait_until_stable_ignores_stale_readings() {
+    let mut sensor = new_mlx90614(&[ta([225, 57, 233]), ta([97, 58, 86])]);
+    let mut monitor = ThermalMonitor::new(0.5);
+    monitor.update(23.0, 0);
+    monitor.update(23.0, 60_000);
+    assert_eq!(Stability::Stable, monitor.stability());
+    let now_ms = clock(&[60_000, 61_500]);
+    let stable = sensor
+        .wait_until_stable(&mut monitor, &mut NoopDelay {}, now_ms, 1000, 1000)
+        .unwrap();
+    assert!(!stable);
+    assert_near!(monitor.slope().unwrap(), 1.71, 0.01);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_classified_object_temperature() {
+    let mut sensor = new_mlx90615(&[
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::TA],
+            vec![59, 58, 4],
+        ),
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::TOBJ],
+            vec![193, 66, 235],
+        ),
+    ]);
+    let mut monitor = ThermalMonitor::new(0.5);
+    monitor.update(24.9, 0);
+    match sensor
+        .object_temperature_with_stability(&mut monitor, 1000)
+        .unwrap()
+    {
+        ObjectReading::Stable(t) => {
+            assert_near!(t, 68.63, 0.01);
+        }
+        _ => panic!("Should have been stable."),
+    }
+    destroy(sensor);
+}
@@ -0,0 +1,106 @@
+mod base;
+use crate::base::{mlx90614, mlx90615};
+use embedded_hal::i2c::{Error as _, ErrorKind};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use mlx9061x::{Clock, Error, Mlx9061x, SlaveAddr, SmbusTimeout, TimeoutError};
+
+struct FakeClock {
+    times_us: Vec<u32>,
+}
+
+impl Clock for FakeClock {
+    fn now_us(&mut self) -> u32 {
+        self.times_us.remove(0)
+    }
+}
+
+fn ta() -> I2cTrans {
+    I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::TA],
+        vec![225, 57, 233],
+    )
+}
+
+fn new(
+    transactions: &[I2cTrans],
+    times_us: &[u32],
+) -> Mlx9061x<SmbusTimeout<I2cMock, FakeClock>, mlx9061x::ic::Mlx90614> {
+    let clock = FakeClock {
+        times_us: times_us.to_vec(),
+    };
+    let i2c = SmbusTimeout::new(I2cMock::new(transactions), clock);
+    Mlx9061x::new_mlx90614(i2c, SlaveAddr::default(), 5).unwrap()
+}
+
+fn destroy(sensor: Mlx9061x<SmbusTimeout<I2cMock, FakeClock>, mlx9061x::ic::Mlx90614>) {
+    let (mut i2c, clock) = sensor.destroy().destroy();
+    i2c.done();
+    assert!(clock.times_us.is_empty());
+}
+
+#[test]
+fn fast_transactions_are_not_reissued() {
+    let mut sensor = new(&[ta()], &[100, 1100]);
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.1);
+    assert_eq!(0, sensor.smbus_timeouts());
+    destroy(sensor);
+}
+
+#[test]
+fn slow_transactions_are_reissued_once() {
+    let mut sensor = new(&[ta(), ta()], &[0, 30_000, 30_000, 31_000]);
+    let t = sensor.ambient_temperature().unwrap();
+    assert_near!(t, 23.19, 0.1);
+    assert_eq!(1, sensor.smbus_timeouts());
+    destroy(sensor);
+}
+
+#[test]
+fn elapsed_time_can_wrap_around() {
+    let mut sensor = new(&[ta()], &[u32::MAX - 100, 900]);
+    sensor.ambient_temperature().unwrap();
+    assert_eq!(0, sensor.smbus_timeouts());
+    destroy(sensor);
+}
+
+#[test]
+fn repeated_timeouts_return_error() {
+    let mut sensor = new(&[ta(), ta()], &[0, 30_000, 30_000, 60_000]);
+    match sensor.ambient_temperature() {
+        Err(Error::I2C(TimeoutError::Timeout)) => (),
+        _ => panic!("Should have returned error."),
+    }
+    assert_eq!(2, sensor.smbus_timeouts());
+    destroy(sensor);
+}
+
+#[test]
+fn timeout_error_kind_maps_through() {
+    assert_eq!(ErrorKind::Other, TimeoutError::<ErrorKind>::Timeout.kind());
+    assert_eq!(ErrorKind::Bus, TimeoutError::I2C(ErrorKind::Bus).kind());
+}
+
+#[test]
+fn timed_out_eeprom_writes_are_not_reissued() {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use mlx90615::{Register as Reg, DEV_ADDR};
+    let pec = smbus_pec::pec(&[DEV_ADDR << 1, Reg::EMISSIVITY, 0, 0]);
+    let clock = FakeClock {
+        times_us: vec![0, 30_000],
+    };
+    let i2c = SmbusTimeout::new(
+        I2cMock::new(&[I2cTrans::write(DEV_ADDR, vec![Reg::EMISSIVITY, 0, 0, pec])]),
+        clock,
+    );
+    let mut sensor = Mlx9061x::new_mlx90615(i2c, SlaveAddr::default(), 5).unwrap();
+    match sensor.set_emissivity(1.0, &mut NoopDelay {}) {
+        Err(Error::I2C(TimeoutError::Timeout)) => (),
+        _ => panic!("Should have returned error."),
+    }
+    assert_eq!(1, sensor.smbus_timeouts());
+    let (mut i2c, clock) = sensor.destroy().destroy();
+    i2c.done();
+    assert!(clock.times_us.is_empty());
+}
@@ -0,0 +1,58 @@
+mod base;
+use crate::base::{destroy, mlx90614, new_mlx90614};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::{AccuracyVariant, Grade, Supply, Variant, Zones};
+
+#[test]
+fn can_decode_part_code() {
+    let variant = Variant::from_code("DCI").unwrap();
+    assert_eq!(Zones::Single, variant.zones);
+    assert_eq!(Some(Supply::V3), variant.supply);
+    assert_eq!(Grade::Medical, variant.grade);
+    assert_eq!(Some(true), variant.gradient_compensated);
+    assert_eq!(Some(5), variant.field_of_view);
+    assert_eq!(
+        AccuracyVariant::Mlx90614(Grade::Medical),
+        variant.accuracy_variant()
+    );
+}
+
+#[test]
+fn can_decode_dual_zone_part_code() {
+    let variant = Variant::from_code("ABA").unwrap();
+    assert_eq!(Zones::Dual, variant.zones);
+    assert_eq!(Some(Supply::V5), variant.supply);
+    assert_eq!(Grade::Standard, variant.grade);
+    assert_eq!(Some(false), variant.gradient_compensated);
+}
+
+#[test]
+fn invalid_part_code_is_rejected() {
+    assert!(Variant::from_code("").is_none());
+    assert!(Variant::from_code("XAA").is_none());
+    assert!(Variant::from_code("AXA").is_none());
+}
+
+#[test]
+fn can_read_variant_once() {
+    let mut sensor = new_mlx90614(&[I2cTrans::write_read(
+        mlx90614::DEV_ADDR,
+        vec![mlx90614::Register::CONFIG1],
+        vec![0xF4, 0x9F, 112],
+    )]);
+    let variant = sensor.variant().unwrap();
+    assert_eq!(Zones::Dual, variant.zones);
+    assert_eq!(Some(12.5), variant.gain);
+    assert_eq!(None, variant.supply);
+    assert_eq!(variant, sensor.variant().unwrap());
+    destroy(sensor);
+}
+
+#[test]
+fn can_set_variant() {
+    let mut sensor = new_mlx90614(&[]);
+    let variant = Variant::from_code("BCC").unwrap();
+    sensor.set_variant(variant);
+    assert_eq!(variant, sensor.variant().unwrap());
+    destroy(sensor);
+}
@@ -0,0 +1,76 @@
+mod base;
+use crate::base::{destroy, mlx90614, mlx90615, new_mlx90614, new_mlx90615};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTrans;
+use mlx9061x::OpticalWindow;
+
+#[test]
+fn out_of_range_transmissivity_is_rejected() {
+    assert!(OpticalWindow::new(0.0).is_none());
+    assert!(OpticalWindow::new(-0.1).is_none());
+    assert!(OpticalWindow::new(1.1).is_none());
+}
+
+#[test]
+fn fully_transparent_window_does_not_change_temperature() {
+    let window = OpticalWindow::new(1.0).unwrap();
+    assert_near!(window.target_temperature(68.63, 24.99, 1.0), 68.63, 0.01);
+}
+
+#[test]
+fn compensates_window_at_ambient_temperature() {
+    let window = OpticalWindow::new(0.5).unwrap();
+    assert_near!(window.target_temperature(68.63, 24.99, 1.0), 100.0, 0.05);
+}
+
+#[test]
+fn compensates_window_temperature() {
+    let window = OpticalWindow::new(0.5).unwrap().with_temperature(60.0);
+    assert_eq!(Some(60.0), window.temperature());
+    assert_near!(window.target_temperature(81.688, 24.99, 1.0), 100.0, 0.05);
+}
+
+#[test]
+fn can_compute_equivalent_emissivity() {
+    let window = OpticalWindow::new(0.5).unwrap();
+    assert_near!(window.equivalent_emissivity(0.9), 0.45, 0.0001);
+}
+
+#[test]
+fn can_read_mlx90614_object_temperature_through_window() {
+    let mut sensor = new_mlx90614(&[
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TOBJ1],
+            vec![193, 66, 55],
+        ),
+        I2cTrans::write_read(
+            mlx90614::DEV_ADDR,
+            vec![mlx90614::Register::TA],
+            vec![59, 58, 216],
+        ),
+    ]);
+    let window = OpticalWindow::new(0.5).unwrap();
+    let t = sensor.object1_temperature_through_window(&window).unwrap();
+    assert_near!(t, 100.0, 0.05);
+    destroy(sensor);
+}
+
+#[test]
+fn can_read_mlx90615_object_temperature_through_window() {
+    let mut sensor = new_mlx90615(&[
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::TOBJ],
+            vec![193, 66, 235],
+        ),
+        I2cTrans::write_read(
+            mlx90615::DEV_ADDR,
+            vec![mlx90615::Register::TA],
+            vec![59, 58, 4],
+        ),
+    ]);
+    let window = OpticalWindow::new(0.5).unwrap();
+    let t = sensor.object_temperature_through_window(&window).unwrap();
+    assert_near!(t, 100.0, 0.05);
+    destroy(sensor);
+}